@@ -0,0 +1,99 @@
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, Div, Mul, Sub};
+use num::{BigInt, ToPrimitive};
+
+/// An integer that can't overflow: a machine integer while the value fits
+/// in one, and a big integer once it doesn't.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Int {
+    Small(i128),
+    Big(BigInt),
+}
+
+impl Int {
+    fn to_big(&self) -> BigInt {
+        match self {
+            Int::Small(n) => BigInt::from(*n),
+            Int::Big(n) => n.clone(),
+        }
+    }
+}
+
+impl From<BigInt> for Int {
+    fn from(value: BigInt) -> Self {
+        value.to_i128().map_or(Int::Big(value), Int::Small)
+    }
+}
+
+macro_rules! int_from {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Int {
+            fn from(value: $t) -> Self {
+                Int::Small(value as i128)
+            }
+        }
+        impl PartialEq<$t> for Int {
+            fn eq(&self, other: &$t) -> bool {
+                matches!(self, Int::Small(n) if *n == *other as i128)
+            }
+        })*
+    };
+}
+int_from!(i32, i64, i128, u32, u64, usize);
+
+macro_rules! int_op {
+    ($($trait:ident, $method:ident, $checked:ident);*) => {
+        $(impl $trait for Int {
+            type Output = Int;
+            fn $method(self, rhs: Int) -> Int {
+                if let (Int::Small(a), Int::Small(b)) = (&self, &rhs) {
+                    if let Some(n) = a.$checked(*b) {
+                        return Int::Small(n);
+                    }
+                }
+                Int::from(self.to_big().$method(rhs.to_big()))
+            }
+        })*
+    };
+}
+int_op!(Add, add, checked_add; Sub, sub, checked_sub; Mul, mul, checked_mul; Div, div, checked_div);
+
+impl Sum for Int {
+    fn sum<I: Iterator<Item=Int>>(iter: I) -> Self {
+        iter.fold(Int::Small(0), |a, b| a + b)
+    }
+}
+
+impl Product for Int {
+    fn product<I: Iterator<Item=Int>>(iter: I) -> Self {
+        iter.fold(Int::Small(1), |a, b| a * b)
+    }
+}
+
+impl fmt::Display for Int {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Int::Small(n) => write!(f, "{n}"),
+            Int::Big(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bignum_test() {
+        let max = Int::from(i128::MAX);
+        let over = max.clone() + Int::from(1);
+        assert!(matches!(over, Int::Big(_)));
+        assert_eq!(over.to_string(), "170141183460469231731687303715884105728");
+        assert_eq!(over.clone() - Int::from(1), max);
+        assert_eq!(over / Int::from(2), Int::from(1u64 << 63) * Int::from(1u64 << 63));
+        assert_eq!((0..5).map(|_| Int::from(u64::MAX)).product::<Int>().to_string(),
+            BigInt::from(u64::MAX).pow(5).to_string());
+        assert_eq!([3, 4].into_iter().map(Int::from).sum::<Int>(), 7);
+    }
+}
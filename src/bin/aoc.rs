@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use advent2023::{days, gen, snapshot};
+use advent2023::runner::{self, Format, Options, Source, OPTIONS_USAGE, STACK_SIZE};
+use advent2023::verify;
+
+fn usage() -> ExitCode {
+    eprintln!("usage: aoc run <day|all> [--jobs N] {OPTIONS_USAGE}");
+    eprintln!("       aoc verify [ANSWERS] [--max-iterations N] [--timeout SECS]");
+    eprintln!("       aoc gen <day> [--size N] [--seed N]");
+    eprintln!("       aoc snapshot [--update]");
+    ExitCode::from(2)
+}
+
+/// Runs every day on `jobs` threads, reporting the results in day order.
+fn run_all(opts: &Options, jobs: usize) -> bool {
+    let all: Vec<_> = days::all().collect();
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|s| {
+        for _ in 0..jobs.min(all.len()) {
+            let tx = tx.clone();
+            let (all, next) = (&all, &next);
+            thread::Builder::new()
+                .stack_size(STACK_SIZE)
+                .spawn_scoped(s, move || loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= all.len() {
+                        break;
+                    }
+                    tx.send((i, runner::execute(all[i], opts))).unwrap();
+                })
+                .unwrap();
+        }
+        drop(tx);
+
+        let mut ok = true;
+        let mut done = BTreeMap::new();
+        let mut shown = 0;
+        for (i, outcome) in rx {
+            done.insert(i, outcome);
+            while let Some(outcome) = done.remove(&shown) {
+                let day = all[shown];
+                if opts.format == Format::Text {
+                    println!("Day {:02}", day.day());
+                }
+                ok &= runner::report(day, opts, outcome);
+                shown += 1;
+            }
+        }
+        ok
+    })
+}
+
+/// Takes `--jobs N` out of `args`, which is only meaningful for `run all`.
+fn take_jobs(args: &mut Vec<String>) -> Result<usize, String> {
+    let Some(pos) = args.iter().position(|a| a == "--jobs" || a.starts_with("--jobs=")) else {
+        return Ok(1);
+    };
+    let arg = args.remove(pos);
+    let value = match arg.split_once('=') {
+        Some((_, v)) => Some(v.to_string()),
+        None => (pos < args.len()).then(|| args.remove(pos)),
+    };
+    match value.map(|v| v.parse::<usize>()) {
+        Some(Ok(n)) if n > 0 => Ok(n),
+        _ => Err("--jobs needs a number of threads".into()),
+    }
+}
+
+fn run(args: &[String]) -> ExitCode {
+    if args.is_empty() {
+        return usage();
+    }
+    let mut rest = args[1..].to_vec();
+    let jobs = match take_jobs(&mut rest) {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            eprintln!("{e}");
+            return usage();
+        },
+    };
+    let opts = match Options::parse(rest) {
+        Ok(opts) => opts,
+        Err(e) => {
+            eprintln!("{e}");
+            return usage();
+        },
+    };
+    let ok = if args[0] == "all" {
+        if matches!(opts.source, Source::Path(_) | Source::Stdin) {
+            eprintln!("`run all` reads each day's own input");
+            return usage();
+        }
+        if opts.render.is_some() || opts.dot.is_some() {
+            eprintln!("`run all` can't write every day to one --render or --dot file");
+            return usage();
+        }
+        if opts.trace {
+            eprintln!("`run all` can't trace; trace one day at a time");
+            return usage();
+        }
+        if !opts.params.is_empty() {
+            eprintln!("`run all` can't take --param; each day has its own");
+            return usage();
+        }
+        if opts.stream {
+            eprintln!("`run all` can't stream; only some days can");
+            return usage();
+        }
+        run_all(&opts, jobs)
+    }
+    else {
+        match args[0].parse().ok().and_then(days::get) {
+            Some(day) => runner::run(day, &opts),
+            None => {
+                eprintln!("no such day: {}", args[0]);
+                return usage();
+            },
+        }
+    };
+    if ok { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
+
+/// Checks the days against a manifest of known answers, by default the
+/// examples' and the real inputs'. The budget options are those of `run`.
+fn verify(args: &[String]) -> ExitCode {
+    let mut path = None;
+    let mut budget_args = Vec::new();
+    let mut itr = args.iter();
+    while let Some(arg) = itr.next() {
+        let name = arg.split_once('=').map_or(arg.as_str(), |(n, _)| n);
+        if name == "--max-iterations" || name == "--timeout" {
+            budget_args.push(arg.clone());
+            if name == arg {
+                budget_args.extend(itr.next().cloned());
+            }
+        } else if path.is_none() && !arg.starts_with('-') {
+            path = Some(PathBuf::from(arg));
+        } else {
+            return usage();
+        }
+    }
+    let budget = match Options::parse(budget_args) {
+        Ok(opts) => opts.budget,
+        Err(e) => {
+            eprintln!("{e}");
+            return usage();
+        },
+    };
+    let paths = path.map_or_else(verify::default_manifests, |p| vec![p]);
+    match verify::load_manifests(&paths) {
+        Ok(manifest) => {
+            if verify::verify(&manifest, budget) { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+        },
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        },
+    }
+}
+
+/// Prints a random input for a day. Without `--seed` a random one is
+/// used, and reported on stderr so the input can be made again.
+fn generate(args: &[String]) -> ExitCode {
+    let Some(day) = args.first().and_then(|a| a.parse().ok()).and_then(days::get) else {
+        return usage();
+    };
+    let mut size = None;
+    let mut seed = None;
+    let mut itr = args[1..].iter();
+    while let Some(arg) = itr.next() {
+        let (name, value) = match arg.split_once('=') {
+            Some((n, v)) => (n, Some(v)),
+            None => (arg.as_str(), itr.next().map(|v| v.as_str())),
+        };
+        let Some(Ok(n)) = value.map(|v| v.parse()) else {
+            eprintln!("{name} needs a number");
+            return usage();
+        };
+        match name {
+            "--size" => { size = Some(n as usize); },
+            "--seed" => { seed = Some(n); },
+            _ => {
+                eprintln!("unknown option {name}");
+                return usage();
+            },
+        }
+    }
+    let seed = seed.unwrap_or_else(|| {
+        let seed = rand::random();
+        eprintln!("seed: {seed}");
+        seed
+    });
+    match day.generate(&mut gen::rng(seed), size) {
+        Some(lines) => {
+            let mut out = io::stdout().lock();
+            for line in lines {
+                // stop quietly if the reader has gone away
+                if writeln!(out, "{line}").is_err() {
+                    break;
+                }
+            }
+            ExitCode::SUCCESS
+        },
+        None => {
+            eprintln!("day {} has no input generator", day.day());
+            ExitCode::FAILURE
+        },
+    }
+}
+
+/// Checks the days' snapshots of their examples, or with `--update`
+/// rewrites the ones that changed.
+fn snapshots(args: &[String]) -> ExitCode {
+    let update = match args {
+        [] => false,
+        [a] if a == "--update" => true,
+        _ => { return usage(); },
+    };
+    match snapshot::check(update) {
+        Ok(changed) => {
+            for (day, problem) in &changed {
+                let what = if update { "updated" } else { "differs" };
+                println!("day{day:02}: {what}: {problem}");
+            }
+            if changed.is_empty() || update { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+        },
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        },
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(|a| a.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("gen") => generate(&args[1..]),
+        Some("snapshot") => snapshots(&args[1..]),
+        _ => usage(),
+    }
+}
@@ -0,0 +1,90 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Limits on the open-ended searches some days do (waiting for a cycle,
+/// sampling until something turns up), so that an input without the
+/// structure a solver expects fails instead of hanging.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Budget {
+    /// Most iterations any one search may take, instead of its own
+    /// default.
+    pub iterations: Option<usize>,
+    /// Longest any one search may run.
+    pub time: Option<Duration>,
+}
+
+impl Budget {
+    /// Starts metering a search for `what`, which by default may take up
+    /// to `iterations` iterations.
+    pub fn start(&self, what: &'static str, iterations: usize) -> Meter {
+        Meter {
+            what,
+            limit: self.iterations.unwrap_or(iterations),
+            time: self.time.map(|t| (t, Instant::now())),
+            count: 0,
+        }
+    }
+}
+
+/// Counts the iterations of one search against its budget.
+#[derive(Clone, Debug)]
+pub struct Meter {
+    what: &'static str,
+    limit: usize,
+    time: Option<(Duration, Instant)>,
+    count: usize,
+}
+
+impl Meter {
+    /// Records an iteration, failing once the search has had its share.
+    pub fn tick(&mut self) -> Result<(), OutOfBudget> {
+        self.count += 1;
+        if self.count > self.limit {
+            return Err(OutOfBudget::Iterations { what: self.what, limit: self.limit });
+        }
+        match self.time {
+            Some((limit, start)) if start.elapsed() > limit => {
+                Err(OutOfBudget::Time { what: self.what, limit })
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A search that gave up without finding what it was looking for.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OutOfBudget {
+    Iterations { what: &'static str, limit: usize },
+    Time { what: &'static str, limit: Duration },
+}
+
+impl fmt::Display for OutOfBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutOfBudget::Iterations { what, limit } => write!(f, "{what} not found within {limit} iterations"),
+            OutOfBudget::Time { what, limit } => write!(f, "{what} not found within {limit:?}"),
+        }
+    }
+}
+
+impl std::error::Error for OutOfBudget {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_test() {
+        let budget = Budget { iterations: Some(3), ..Budget::default() };
+        let mut meter = budget.start("cycle", 100);
+        assert!((0..3).all(|_| meter.tick().is_ok()));
+        let err = meter.tick().unwrap_err();
+        assert_eq!(err, OutOfBudget::Iterations { what: "cycle", limit: 3 });
+        assert_eq!(err.to_string(), "cycle not found within 3 iterations");
+
+        let budget = Budget { time: Some(Duration::ZERO), ..Budget::default() };
+        let mut meter = budget.start("cycle", 100);
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(matches!(meter.tick(), Err(OutOfBudget::Time { .. })));
+    }
+}
@@ -0,0 +1,514 @@
+//! Day 1: Trebuchet?!
+
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::vec::Vec;
+use itertools::Itertools;
+use json::{object, JsonValue};
+use rand::Rng;
+use ya_advent_lib::iter::FirstLast;
+use crate::gen::StdRng;
+use crate::parse::{ParseError, next_token, parse_lines, parse_token};
+use crate::runner::{Answer, Context, Day, TextParam};
+
+/// Sum of the calibration values: the first and last digit on each line.
+/// None if a line has no digits.
+pub fn part1(input: &[String]) -> Option<u64> {
+    input.iter()
+        .map(|s| {
+            s.chars()
+                .filter(|&c| ('1'..='9').contains(&c))
+                .first_last()
+                .map(|(f,l)| (f as u64 - '0' as u64) * 10 + (l as u64 - '0' as u64))
+        })
+        .sum()
+}
+
+/// A word (or digit) part 2 reads as a digit, and its value.
+struct Token {
+    word: String,
+    value: u64,
+}
+
+impl FromStr for Token {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut itr = s.split_whitespace();
+        let word = next_token(s, &mut itr, "a word")?.to_string();
+        let value = parse_token(s, next_token(s, &mut itr, "its value")?, "a number")?;
+        Ok(Token { word, value })
+    }
+}
+
+/// A matcher for many words at once (Aho-Corasick): a state for each
+/// prefix of the words, with a transition for every byte, so finding
+/// them all takes one pass over a line however many there are.
+struct Automaton {
+    next: Vec<[u32; 256]>,
+    /// The words (by index) that end on reaching each state.
+    found: Vec<Vec<usize>>,
+}
+
+impl Automaton {
+    fn new<W: IntoIterator<Item=u8>>(words: impl IntoIterator<Item=W>) -> Self {
+        let mut next = vec![[0; 256]];
+        let mut found = vec![Vec::new()];
+        for (idx, word) in words.into_iter().enumerate() {
+            let mut state = 0;
+            for b in word {
+                if next[state][b as usize] == 0 {
+                    next[state][b as usize] = next.len() as u32;
+                    next.push([0; 256]);
+                    found.push(Vec::new());
+                }
+                state = next[state][b as usize] as usize;
+            }
+            found[state].push(idx);
+        }
+        // Breadth first, so a state's fallback (its longest proper suffix
+        // that's also a prefix) is complete before the state needs it.
+        // Missing transitions become the fallback's.
+        let mut fallback = vec![0; next.len()];
+        let mut queue: VecDeque<usize> = next[0].iter().map(|s| *s as usize).filter(|s| *s != 0).collect();
+        while let Some(state) = queue.pop_front() {
+            let fallback_next = next[fallback[state]];
+            for (b, &via) in fallback_next.iter().enumerate() {
+                let child = next[state][b] as usize;
+                if child == 0 {
+                    next[state][b] = via;
+                }
+                else {
+                    fallback[child] = via as usize;
+                    let inherited = found[via as usize].clone();
+                    found[child].extend(inherited);
+                    queue.push_back(child);
+                }
+            }
+        }
+        Automaton { next, found }
+    }
+
+    fn step(&self, state: usize, b: u8) -> usize {
+        self.next[state][b as usize] as usize
+    }
+}
+
+/// Number words for the built-in vocabularies, one to nine, then zero
+/// and ten.
+const LANGUAGES: &[(&str, [&str; 11])] = &[
+    ("english", ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "zero", "ten"]),
+    ("german", ["eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun", "null", "zehn"]),
+    ("french", ["un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf", "zéro", "dix"]),
+    ("spanish", ["uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve", "cero", "diez"]),
+];
+
+/// The tokens part 2 reads as digits: the digits themselves and some
+/// number words.
+pub struct Vocabulary {
+    tokens: Vec<Token>,
+    /// Length of the longest token.
+    longest: usize,
+    /// Matches the tokens reading forwards.
+    forward: Automaton,
+    /// Matches the tokens reading backwards, from the end of a line.
+    backward: Automaton,
+}
+
+impl Vocabulary {
+    fn new(tokens: Vec<Token>) -> Self {
+        Vocabulary {
+            longest: tokens.iter().map(|t| t.word.len()).max().unwrap_or(0),
+            forward: Automaton::new(tokens.iter().map(|t| t.word.bytes())),
+            backward: Automaton::new(tokens.iter().map(|t| t.word.bytes().rev())),
+            tokens,
+        }
+    }
+
+    /// Just the digits 1 to 9, as part 1 reads them.
+    pub fn digits() -> Self {
+        Vocabulary::new((1..=9).map(|n| Token { word: n.to_string(), value: n }).collect())
+    }
+
+    /// The digits 1 to 9 and the words for them in `language`, with
+    /// zero (and the digit 0) and ten if asked for. None if there's no
+    /// such built-in language.
+    pub fn builtin(language: &str, zero: bool, ten: bool) -> Option<Self> {
+        let (_, words) = LANGUAGES.iter().find(|(name, _)| *name == language)?;
+        let mut tokens: Vec<Token> = (1..=9)
+            .map(|n| Token { word: n.to_string(), value: n })
+            .chain(words[..9].iter().zip(1..).map(|(w, n)| Token { word: w.to_string(), value: n }))
+            .collect();
+        if zero {
+            tokens.push(Token { word: "0".into(), value: 0 });
+            tokens.push(Token { word: words[9].into(), value: 0 });
+        }
+        if ten {
+            tokens.push(Token { word: words[10].into(), value: 10 });
+        }
+        Some(Vocabulary::new(tokens))
+    }
+
+    /// The vocabulary `spec` names: a built-in language optionally
+    /// followed by `+zero` and/or `+ten` (like `german+zero`), or else a
+    /// file with a `WORD VALUE` line for each token.
+    pub fn from_spec(spec: &str) -> Result<Self, String> {
+        let mut parts = spec.split('+');
+        let language = parts.next().unwrap_or_default();
+        let extras: Vec<&str> = parts.collect();
+        if LANGUAGES.iter().any(|(name, _)| *name == language) {
+            if let Some(extra) = extras.iter().find(|e| !["zero", "ten"].contains(e)) {
+                return Err(format!("unknown addition {extra}; expected zero or ten"));
+            }
+            return Ok(Self::builtin(language, extras.contains(&"zero"), extras.contains(&"ten")).unwrap());
+        }
+        let text = fs::read_to_string(spec).map_err(|e| {
+            let names: Vec<&str> = LANGUAGES.iter().map(|(name, _)| *name).collect();
+            format!("{spec} is neither a built-in vocabulary ({}) nor a readable file: {e}", names.join(", "))
+        })?;
+        let tokens: Vec<Token> = parse_lines(text.lines().map(String::from))
+            .map_err(|e| format!("{spec}: {e}"))?;
+        if tokens.is_empty() {
+            return Err(format!("{spec}: no words"));
+        }
+        Ok(Vocabulary::new(tokens))
+    }
+
+    /// The calibration value of `s`: the values of its first and last
+    /// tokens. Tokens may overlap, so `eightwo` reads as 82. Where two
+    /// tokens start at the same place the longer one counts.
+    pub fn calibration(&self, s: &str) -> Option<u64> {
+        let ((_, first), (_, last)) = (self.first(s)?, self.last(s)?);
+        Some(self.tokens[first].value * 10 + self.tokens[last].value)
+    }
+
+    /// The byte offset and index of the token that starts first in `s`.
+    /// The one that ends first may not be it, so this carries on until no
+    /// later match could start any earlier.
+    fn first(&self, s: &str) -> Option<(usize, usize)> {
+        let mut state = 0;
+        let mut best: Option<(usize, Reverse<usize>, usize)> = None;
+        for (idx, b) in s.bytes().enumerate() {
+            if best.is_some_and(|(start, _, _)| idx >= start + self.longest) {
+                break;
+            }
+            state = self.forward.step(state, b);
+            for &t in &self.forward.found[state] {
+                let len = self.tokens[t].word.len();
+                let candidate = (idx + 1 - len, Reverse(len), t);
+                if best.is_none_or(|b| candidate < b) {
+                    best = Some(candidate);
+                }
+            }
+        }
+        best.map(|(start, _, t)| (start, t))
+    }
+
+    /// The byte offset and index of the token that starts last in `s`.
+    /// Reading backwards, every match found at a position starts there,
+    /// so the first position with any is the answer.
+    fn last(&self, s: &str) -> Option<(usize, usize)> {
+        let mut state = 0;
+        for (idx, b) in s.bytes().enumerate().rev() {
+            state = self.backward.step(state, b);
+            if let Some(&t) = self.backward.found[state].iter().max_by_key(|t| self.tokens[**t].word.len()) {
+                return Some((idx, t));
+            }
+        }
+        None
+    }
+
+    /// What `calibration` found in `s`, and where, as a row of the
+    /// diagnostics.
+    fn diagnose(&self, s: &str) -> JsonValue {
+        let (first, last) = (self.first(s), self.last(s));
+        let word = |found: Option<(usize, usize)>| found.map(|(_, t)| self.tokens[t].word.as_str());
+        let problem = match (first, last) {
+            (Some(f), Some(l)) if f.0 == l.0 => Some("one digit"),
+            (None, _) | (_, None) => Some("no digits"),
+            _ => None,
+        };
+        object!{
+            first: word(first),
+            first_offset: first.map(|f| f.0),
+            last: word(last),
+            last_offset: last.map(|l| l.0),
+            value: self.calibration(s),
+            problem: problem,
+        }
+    }
+
+    /// Reference for `calibration`: the original matcher, which finds
+    /// every match of each token separately and sorts them. Where two
+    /// tokens start at the same place either may count, so it agrees with
+    /// `calibration` only when no token is a prefix of another (as in the
+    /// built-in vocabularies). `aoc run 1 --bench N --reference` times it
+    /// against the automaton.
+    fn calibration_reference(&self, s: &str) -> Option<u64> {
+        self.tokens.iter()
+            .flat_map(|t| s.match_indices(t.word.as_str()).map(move |(idx, _)| (idx, t.value)))
+            .sorted_unstable_by_key(|t| t.0)
+            .map(|(_, v)| v)
+            .first_last()
+            .map(|(f, l)| f * 10 + l)
+    }
+}
+
+impl Default for Vocabulary {
+    fn default() -> Self {
+        Self::builtin("english", false, false).unwrap()
+    }
+}
+
+const VOCABULARY: TextParam = TextParam {
+    name: "vocabulary",
+    default: "english",
+    help: "number words for part 2: english, german, french or spanish, with +zero or +ten, or a file of WORD VALUE lines",
+    check: |spec| Vocabulary::from_spec(spec).map(|_| ()),
+};
+
+/// The calibration document, and the vocabulary part 2 reads it with,
+/// built on first use from the `vocabulary` param.
+pub struct Input {
+    lines: Vec<String>,
+    vocabulary: OnceLock<Vocabulary>,
+}
+
+impl Input {
+    /// The vocabulary `ctx` asks for.
+    pub fn vocabulary(&self, ctx: &Context) -> &Vocabulary {
+        self.vocabulary.get_or_init(|| vocabulary(ctx))
+    }
+}
+
+/// The vocabulary `ctx` asks for, which `check_params` has already found
+/// to be a good one.
+fn vocabulary(ctx: &Context) -> Vocabulary {
+    Vocabulary::from_spec(ctx.text_param(&VOCABULARY)).expect("vocabulary was checked with the params")
+}
+
+/// Like part 1, but digits may also be spelled out.
+pub fn part2(input: &[String], vocabulary: &Vocabulary, ctx: &Context) -> Option<u64> {
+    let calibration = if ctx.reference { Vocabulary::calibration_reference } else { Vocabulary::calibration };
+    input.iter()
+        .map(|s| calibration(vocabulary, s))
+        .sum()
+}
+
+/// Both parts' answers for lines read one at a time from `reader`,
+/// holding only the current one, so an input of any size can be
+/// summed. `progress` hears the number of lines read and the totals so
+/// far after each line. A line a part finds no digit on is left out of
+/// its total, and `skipped` hears its number and the part.
+pub fn stream<R: BufRead>(
+    mut reader: R,
+    vocabulary: &Vocabulary,
+    ctx: &Context,
+    mut progress: impl FnMut(usize, [u64; 2]),
+    mut skipped: impl FnMut(usize, usize),
+) -> io::Result<[u64; 2]> {
+    let calibration = if ctx.reference { Vocabulary::calibration_reference } else { Vocabulary::calibration };
+    let digits = Vocabulary::digits();
+    let mut totals = [0, 0];
+    let mut line = String::new();
+    let mut lines = 0;
+    while reader.read_line(&mut line)? > 0 {
+        let s = line.trim_end_matches(['\n', '\r']);
+        lines += 1;
+        for (part, (total, v)) in totals.iter_mut().zip([&digits, vocabulary]).enumerate() {
+            match calibration(v, s) {
+                Some(c) => { *total += c; },
+                None => skipped(lines, part + 1),
+            }
+        }
+        progress(lines, totals);
+        line.clear();
+    }
+    Ok(totals)
+}
+
+/// The first and last tokens each part finds on each line, and the
+/// calibration value they give, flagging lines with fewer than two.
+fn diagnostics(input: &[String], vocabulary: &Vocabulary) -> Vec<JsonValue> {
+    let digits = Vocabulary::digits();
+    input.iter()
+        .enumerate()
+        .flat_map(|(idx, s)| [(1, &digits), (2, vocabulary)].map(|(part, v)| {
+            let mut row = object!{ line: idx + 1, part: part };
+            for (name, value) in v.diagnose(s).entries() {
+                row[name] = value.clone();
+            }
+            row
+        }))
+        .collect()
+}
+
+/// Lines of letters, digits and spelled-out digits, each with at least
+/// one digit.
+fn generate(rng: &mut StdRng, lines: usize) -> Vec<String> {
+    let words = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+    (0..lines)
+        .map(|_| {
+            let mut s = String::new();
+            for _ in 0..rng.gen_range(1..=6) {
+                match rng.gen_range(0..3) {
+                    0 => s.push(rng.gen_range('1'..='9')),
+                    1 => s.push_str(words[rng.gen_range(0..words.len())]),
+                    _ => (0..rng.gen_range(1..=3)).for_each(|_| s.push(rng.gen_range('a'..='z'))),
+                }
+            }
+            if !s.chars().any(|c| c.is_ascii_digit()) {
+                s.insert(rng.gen_range(0..=s.len()), rng.gen_range('1'..='9'));
+            }
+            s
+        })
+        .collect()
+}
+
+pub struct Day01;
+
+impl Day for Day01 {
+    const DAY: u32 = 1;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day01.testinput"), include_str!("day01.testinput2")];
+    const TEXT_PARAMS: &'static [TextParam] = &[VOCABULARY];
+    type Input = Input;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        Ok(Input { lines, vocabulary: OnceLock::new() })
+    }
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part1(&input.lines).into()
+    }
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part2(&input.lines, input.vocabulary(ctx), ctx).into()
+    }
+    fn diagnostics(&self, input: &Self::Input, ctx: &Context) -> Vec<JsonValue> {
+        diagnostics(&input.lines, input.vocabulary(ctx))
+    }
+    fn stream(&self, reader: &mut dyn BufRead, ctx: &Context, progress: &mut dyn FnMut(usize, [Answer; 2])) -> Option<io::Result<[Answer; 2]>> {
+        Some(stream(
+            reader,
+            &vocabulary(ctx),
+            ctx,
+            |lines, totals| progress(lines, totals.map(Answer::from)),
+            |line, part| eprintln!("day01: line {line}: part {part} found no digit, leaving it out"),
+        ).map(|totals| totals.map(Answer::from)))
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(1000)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use ya_advent_lib::read::test_input;
+    use crate::gen;
+
+    #[test]
+    fn day01_test() {
+        assert_eq!(
+            part1(&test_input(include_str!("day01.testinput"))),
+            Some(142)
+        );
+        assert_eq!(
+            part2(&test_input(include_str!("day01.testinput2")), &Vocabulary::default(), &Context::default()),
+            Some(281)
+        );
+    }
+
+    #[test]
+    fn day01_vocabulary_test() {
+        let german = Vocabulary::from_spec("german+zero").unwrap();
+        assert_eq!(german.calibration("achtzweins"), Some(81));
+        assert_eq!(german.calibration("nullxfünf"), Some(5));
+        assert_eq!(german.calibration("eightwo"), None);
+        let english = Vocabulary::from_spec("english+ten").unwrap();
+        assert_eq!(english.calibration("eightwo"), Some(82));
+        assert_eq!(english.calibration("tenine"), Some(109));
+        assert!(Vocabulary::from_spec("english+eleven").is_err());
+
+        let ctx = Context { params: BTreeMap::from([("vocabulary".into(), "german".into())]), ..Context::default() };
+        let input = Day01.parse(vec!["zweixacht".into()]).unwrap();
+        assert_eq!(Day01.part2(&input, &ctx).to_string(), "28");
+    }
+
+    #[test]
+    fn day01_reference_test() {
+        // Tokens that start inside and at the same place as others, where
+        // the longest one that starts first and last counts.
+        let nested = Vocabulary::new(["abcd", "bc", "aa", "a", "cdab"].iter().zip(1..)
+            .map(|(w, value)| Token { word: w.to_string(), value })
+            .collect());
+        assert_eq!(nested.calibration("abcdab"), Some(14));
+        assert_eq!(nested.calibration("aab"), Some(34));
+        assert_eq!(nested.calibration("xbcdabx"), Some(24));
+        assert_eq!(nested.calibration("xbcx"), Some(22));
+
+        let vocabularies: Vec<Vocabulary> = LANGUAGES.iter()
+            .map(|(name, _)| Vocabulary::builtin(name, true, true).unwrap())
+            .collect();
+        for seed in 0..20 {
+            let mut rng = gen::rng(seed);
+            for vocabulary in &vocabularies {
+                for _ in 0..50 {
+                    // Pieces of tokens run together, so that they overlap
+                    // and break off part way.
+                    let s: String = (0..rng.gen_range(0..8))
+                        .map(|_| {
+                            let word = &vocabulary.tokens[rng.gen_range(0..vocabulary.tokens.len())].word;
+                            let mut end = rng.gen_range(1..=word.len());
+                            while !word.is_char_boundary(end) { end += 1; }
+                            word[..end].to_string()
+                        })
+                        .collect();
+                    assert_eq!(vocabulary.calibration(&s), vocabulary.calibration_reference(&s), "{s}");
+                }
+            }
+            let input = generate(&mut rng, 100);
+            let reference = Context { reference: true, ..Context::default() };
+            assert_eq!(part2(&input, &vocabularies[0], &Context::default()), part2(&input, &vocabularies[0], &reference));
+        }
+    }
+
+    #[test]
+    fn day01_diagnostics_test() {
+        let input: Vec<String> = test_input(include_str!("day01.testinput2"));
+        assert_eq!(part1(&input), None);
+        let rows = diagnostics(&input, &Vocabulary::default());
+        // two1nine
+        assert_eq!(rows[0]["problem"], "one digit");
+        assert_eq!(rows[1], object!{
+            line: 1, part: 2, first: "two", first_offset: 0, last: "nine", last_offset: 4, value: 29, problem: null,
+        });
+        // eightwothree
+        assert_eq!(rows[2], object!{
+            line: 2, part: 1, first: null, first_offset: null, last: null, last_offset: null, value: null, problem: "no digits",
+        });
+        // abcone2threexyz
+        assert_eq!(rows[4]["problem"], "one digit");
+        assert_eq!(rows[5]["last"], "three");
+        assert_eq!(rows[5]["last_offset"], 7);
+    }
+
+    #[test]
+    fn day01_stream_test() {
+        let streamed = |input: &str| stream(input.as_bytes(), &Vocabulary::default(), &Context::default(), |_, _| (), |_, _| ()).unwrap();
+        assert_eq!(streamed(include_str!("day01.testinput")), [142, 142]);
+        assert_eq!(streamed(include_str!("day01.testinput2"))[1], 281);
+
+        let (mut seen, mut skipped) = (Vec::new(), Vec::new());
+        stream(
+            "1abc2\nxyz\n3x4\nsix\n".as_bytes(),
+            &Vocabulary::default(),
+            &Context::default(),
+            |lines, totals| seen.push((lines, totals)),
+            |line, part| skipped.push((line, part)),
+        ).unwrap();
+        assert_eq!(seen, [(1, [12, 12]), (2, [12, 12]), (3, [46, 46]), (4, [46, 112])]);
+        assert_eq!(skipped, [(2, 1), (2, 2), (4, 1)]);
+    }
+}
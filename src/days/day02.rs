@@ -0,0 +1,161 @@
+//! Day 2: Cube Conundrum
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::vec::Vec;
+use rand::Rng;
+use crate::gen::StdRng;
+use crate::parse::{ParseError, next_token, parse_lines, parse_token};
+use crate::runner::{Answer, Context, Day, TextParam};
+
+/// A handful of cubes: how many of each color. Colors not mentioned
+/// have none.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Cubes(BTreeMap<String, usize>);
+
+impl Cubes {
+    /// How many `color` cubes there are.
+    pub fn count(&self, color: &str) -> usize {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+}
+
+impl FromStr for Cubes {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cubes = BTreeMap::new();
+        for ss in s.split(", ") {
+            let mut itr = ss.split(' ');
+            let n = parse_token(s, next_token(s, &mut itr, "a cube count")?, "a cube count")?;
+            let color = next_token(s, &mut itr, "a color")?;
+            if color.is_empty() || !color.chars().all(char::is_alphabetic) {
+                return Err(ParseError::at(s, color, "a color"));
+            }
+            cubes.insert(color.to_string(), n);
+        }
+        Ok(Cubes(cubes))
+    }
+}
+
+/// One game: its ID and the sets of cubes revealed.
+pub struct Game {
+    id: usize,
+    sets: Vec<Cubes>,
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (g, sets) = s.split_once(": ").ok_or_else(|| ParseError::missing(s, "`: `"))?;
+        let id = parse_token(s, g.split(' ').next_back().unwrap(), "a game id")?;
+        let sets = sets.split("; ")
+            .map(|x| x.parse::<Cubes>().map_err(|e| e.within(s, x)))
+            .collect::<Result<_, _>>()?;
+        Ok(Game{id, sets})
+    }
+}
+
+const BAG: TextParam = TextParam {
+    name: "bag",
+    default: "12 red, 13 green, 14 blue",
+    help: "the cubes in the bag, written like a set in a game",
+    check: |bag| bag.parse::<Cubes>().map(|_| ()).map_err(|e| e.to_string()),
+};
+
+/// Whether every set in the game could have come from `bag`.
+fn possible(game: &Game, bag: &Cubes) -> bool {
+    game.sets.iter()
+        .all(|set| set.0.iter().all(|(color, n)| *n <= bag.count(color)))
+}
+
+/// Sum of the IDs of games possible with the cubes in the `bag` param.
+pub fn part1(input: &[Game], ctx: &Context) -> usize {
+    let bag: Cubes = ctx.text_param(&BAG).parse().expect("bag was checked with the params");
+    input.iter()
+        .filter(|game| possible(game, &bag))
+        .map(|game| game.id)
+        .sum()
+}
+
+/// The product of the most cubes of each color seen in the game.
+fn power(game: &Game) -> usize {
+    let mut most: BTreeMap<&str, usize> = BTreeMap::new();
+    for (color, n) in game.sets.iter().flat_map(|set| set.0.iter()) {
+        let m = most.entry(color).or_default();
+        *m = (*m).max(*n);
+    }
+    most.values().product()
+}
+
+/// Sum of the powers of the fewest cubes that make each game possible.
+pub fn part2(input: &[Game]) -> usize {
+    input.iter()
+        .map(power)
+        .sum()
+}
+
+fn generate(rng: &mut StdRng, games: usize) -> Vec<String> {
+    (1..=games)
+        .map(|id| {
+            let sets = (0..rng.gen_range(1..=6))
+                .map(|_| {
+                    let mut colors = vec!["red", "green", "blue"];
+                    colors.retain(|_| rng.gen_bool(0.7));
+                    if colors.is_empty() {
+                        colors.push("red");
+                    }
+                    colors.iter()
+                        .map(|c| format!("{} {c}", rng.gen_range(1..=20)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect::<Vec<_>>();
+            format!("Game {id}: {}", sets.join("; "))
+        })
+        .collect()
+}
+
+pub struct Day02;
+
+impl Day for Day02 {
+    const DAY: u32 = 2;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day02.testinput")];
+    const TEXT_PARAMS: &'static [TextParam] = &[BAG];
+    type Input = Vec<Game>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        parse_lines(lines)
+    }
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part1(input, ctx).into()
+    }
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part2(input).into()
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(100)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day02_test() {
+        let input: Vec<Game> = test_input(include_str!("day02.testinput"));
+        assert_eq!(part1(&input, &Context::default()), 8);
+        assert_eq!(part2(&input), 2286);
+
+        let input: Vec<Game> = test_input("\
+Game 1: 2 yellow, 1 red; 3 purple
+Game 2: 5 purple, 1 yellow
+");
+        let bag = "1 red, 2 yellow, 3 purple".parse().unwrap();
+        assert!(possible(&input[0], &bag));
+        assert!(!possible(&input[1], &bag));
+        let params = [("bag".to_string(), "5 purple, 1 yellow".to_string())].into();
+        assert_eq!(part1(&input, &Context { params, ..Context::default() }), 2);
+        assert_eq!(part2(&input), 6 + 5);
+    }
+}
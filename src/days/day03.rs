@@ -0,0 +1,183 @@
+//! Day 3: Gear Ratios
+
+use std::collections::HashMap;
+use std::vec::Vec;
+use rand::Rng;
+use ya_advent_lib::coords::Coord2D;
+use ya_advent_lib::grid::Grid;
+use crate::gen::StdRng;
+use crate::parse::ParseError;
+use crate::render::{Image, Rgb};
+use crate::runner::{Answer, Context, Day};
+
+fn is_symbol(grid: &Grid<char>, x: i64, y: i64) -> bool {
+    !matches!(grid.get(x, y), '.' | '0'..='9')
+}
+
+fn is_gear(grid: &Grid<char>, x: i64, y: i64) -> bool {
+    grid.get(x, y) == '*'
+}
+
+fn get_number(grid: &Grid<char>, x: i64, y: i64, gear_map: &mut HashMap<(i64,i64),Vec<u64>>) -> (u64, bool, i64) {
+    let mut s: String = String::new();
+    let mut next_x = x;
+    for x1 in x.. {
+        let c = grid.get(x1, y);
+        match c {
+            '0'..='9' => { s.push(c); }
+            _ => { next_x = x1; break; }
+        }
+    }
+    let v = s.parse::<u64>().unwrap();
+    let mut is_pn = false;
+    for x1 in (x-1)..=next_x {
+        if is_symbol(grid, x1, y - 1) {
+            is_pn = true;
+        }
+        if is_symbol(grid, x1, y + 1) {
+            is_pn = true;
+        }
+        if (x1 == x-1 || x1 == next_x) && is_symbol(grid, x1, y) {
+            is_pn = true;
+        }
+        if is_gear(grid, x1, y - 1) {
+            gear_map.entry((x1, y-1)).and_modify(|n| n.push(v)).or_insert(vec![v]);
+        }
+        if is_symbol(grid, x1, y + 1) {
+            gear_map.entry((x1, y+1)).and_modify(|n| n.push(v)).or_insert(vec![v]);
+        }
+        if (x1 == x-1 || x1 == next_x) && is_symbol(grid, x1, y) {
+            gear_map.entry((x1, y)).and_modify(|n| n.push(v)).or_insert(vec![v]);
+        }
+    }
+    //println!("{x},{y}: {v} {is_pn} {next_x}");
+    (v, is_pn, next_x)
+}
+
+fn bothparts(input: &[String]) -> (u64, u64) {
+    let grid: Grid<char> = Grid::from_input(input, '.', 1);
+    let mut sum = 0;
+    let x_width = grid.x_bounds_orig().end;
+    let y_width = grid.y_bounds_orig().end;
+    let mut gear_map: HashMap<(i64,i64),Vec<u64>> = HashMap::new();
+    for y in 0..y_width {
+        let mut x = 0;
+        while x < x_width {
+            match grid.get(x, y) {
+                '0'..='9' => {
+                    let (n, is_pn, next_x) = get_number(&grid, x, y, &mut gear_map);
+                    if is_pn {
+                        sum += n;
+                    }
+                    x = next_x;
+                },
+                _ => { x += 1; },
+            }
+        }
+    }
+    let gears = gear_map.iter()
+        .filter(|(_, v)| v.len() == 2)
+        .map(|(_, v)| v.iter().product::<u64>())
+        .sum();
+    (sum, gears)
+}
+
+/// Sum of the part numbers: numbers next to a symbol.
+pub fn part1(input: &[String]) -> u64 {
+    bothparts(input).0
+}
+
+/// Sum of the gear ratios of `*`s next to exactly two part numbers.
+pub fn part2(input: &[String]) -> u64 {
+    bothparts(input).1
+}
+
+/// Part numbers in green, other numbers in red, and gears in yellow.
+fn render(input: &[String]) -> Image {
+    let grid: Grid<char> = Grid::from_input(input, '.', 1);
+    let mut image = Image::from_grid(&grid, |c| match c {
+        '.' => Rgb::BLACK,
+        _ => Rgb::WHITE,
+    });
+    let mut gear_map: HashMap<(i64,i64),Vec<u64>> = HashMap::new();
+    for y in grid.y_bounds_orig() {
+        let mut x = 0;
+        while x < grid.x_bounds_orig().end {
+            match grid.get(x, y) {
+                '0'..='9' => {
+                    let (_, is_pn, next_x) = get_number(&grid, x, y, &mut gear_map);
+                    let color = if is_pn { Rgb::GREEN } else { Rgb::RED };
+                    image.overlay((x..next_x).map(|x1| Coord2D::new(x1, y)), color);
+                    x = next_x;
+                },
+                _ => { x += 1; },
+            }
+        }
+    }
+    image.overlay(
+        gear_map.iter()
+            .filter(|((x, y), v)| v.len() == 2 && is_gear(&grid, *x, *y))
+            .map(|((x, y), _)| Coord2D::new(*x, *y)),
+        Rgb::YELLOW,
+    );
+    image
+}
+
+/// A `size` by `size` schematic of numbers and symbols.
+fn generate(rng: &mut StdRng, size: usize) -> Vec<String> {
+    let symbols = ['*', '*', '#', '+', '$', '/', '=', '%', '@', '&', '-'];
+    (0..size)
+        .map(|_| {
+            let mut row = String::new();
+            while row.len() < size {
+                match rng.gen_range(0..10) {
+                    0..=1 => {
+                        let n = rng.gen_range(1..1000u32).to_string();
+                        row.push_str(&n[..n.len().min(size - row.len())]);
+                        if row.len() < size {
+                            row.push('.');
+                        }
+                    },
+                    2 => row.push(symbols[rng.gen_range(0..symbols.len())]),
+                    _ => row.push('.'),
+                }
+            }
+            row
+        })
+        .collect()
+}
+
+pub struct Day03;
+
+impl Day for Day03 {
+    const DAY: u32 = 3;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day03.testinput")];
+    type Input = Vec<String>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        Ok(lines)
+    }
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part1(input).into()
+    }
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part2(input).into()
+    }
+    fn render(&self, input: &Self::Input, _ctx: &Context) -> Option<Image> {
+        Some(render(input))
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(140)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day03_test() {
+        let input: Vec<String> = test_input(include_str!("day03.testinput"));
+        assert_eq!(bothparts(&input), (4361, 467835));
+    }
+}
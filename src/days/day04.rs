@@ -0,0 +1,105 @@
+//! Day 4: Scratchcards
+
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::vec::Vec;
+use itertools::Itertools;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use rand::seq::index::sample;
+use crate::gen::StdRng;
+use crate::parse::{ParseError, next_token, parse_lines, parse_token};
+use crate::runner::{Answer, Context, Day};
+
+/// One scratchcard.
+pub struct Input {
+    winners: HashSet<u32>,
+    have: HashSet<u32>,
+}
+
+impl FromStr for Input {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (_, nums) = s.split_once(": ").ok_or_else(|| ParseError::missing(s, "`: `"))?;
+        let mut itr = nums.split(" | ");
+        let winners = next_token(s, &mut itr, "winning numbers")?.split_whitespace()
+            .map(|n| parse_token(s, n, "a number"))
+            .collect::<Result<_, _>>()?;
+        let have = next_token(s, &mut itr, "` | ` and the numbers you have")?.split_whitespace()
+            .map(|n| parse_token(s, n, "a number"))
+            .collect::<Result<_, _>>()?;
+        Ok(Input{winners, have})
+    }
+}
+
+/// Total points of the scratchcards.
+pub fn part1(input: &[Input]) -> u32 {
+    input.iter()
+        .map(|card| card.winners.intersection(&card.have).count())
+        .map(|c| match c { 0 => 0, n => 2u32.pow(n as u32 - 1) })
+        .sum()
+}
+
+/// How many scratchcards you end up with, counting the copies won.
+pub fn part2(input: &[Input]) -> usize {
+    let mut counts: Vec<usize> = vec![1; input.len()];
+
+    input.iter()
+        .enumerate()
+        .for_each(|(idx, card)| {
+            let w = card.winners.intersection(&card.have).count();
+            for n in idx + 1 .. (idx + 1 + w).min(counts.len()) {
+                counts[n] += counts[idx];
+            }
+        });
+    counts.iter().sum()
+}
+
+/// Cards where a card with `n` matches is half as likely as one with
+/// `n - 1`, to keep the number of won copies reasonable.
+fn generate(rng: &mut StdRng, cards: usize) -> Vec<String> {
+    let fmt = |nums: &[usize]| nums.iter().map(|n| format!("{:>2}", n + 1)).join(" ");
+    (1..=cards)
+        .map(|id| {
+            let nums = sample(rng, 99, 35).into_vec();
+            let matches = (0..10).take_while(|_| rng.gen_bool(0.5)).count();
+            let (winners, rest) = nums.split_at(10);
+            let mut have = [&winners[..matches], &rest[..25 - matches]].concat();
+            have.shuffle(rng);
+            format!("Card {id:>3}: {} | {}", fmt(winners), fmt(&have))
+        })
+        .collect()
+}
+
+pub struct Day04;
+
+impl Day for Day04 {
+    const DAY: u32 = 4;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day04.testinput")];
+    type Input = Vec<Input>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        parse_lines(lines)
+    }
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part1(input).into()
+    }
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part2(input).into()
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(200)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day04_test() {
+        let input: Vec<Input> = test_input(include_str!("day04.testinput"));
+        assert_eq!(part1(&input), 13);
+        assert_eq!(part2(&input), 30);
+    }
+}
@@ -0,0 +1,285 @@
+//! Day 5: If You Give A Seed A Fertilizer
+
+use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
+use std::str::FromStr;
+use std::vec::Vec;
+use itertools::Itertools;
+use rand::Rng;
+use crate::gen::StdRng;
+use crate::parse::{ParseError, next_token, parse_grouped, parse_token};
+use crate::runner::{Answer, Context, Day};
+
+#[derive(Debug)]
+struct SeedMapEntry {
+    from: Range<u64>,
+    to: u64
+}
+
+impl FromStr for SeedMapEntry {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut itr = s.split(' ');
+        let mut num = || parse_token::<u64>(s, next_token(s, &mut itr, "a number")?, "a number");
+        let (to, from, len) = (num()?, num()?, num()?);
+        if let Some(extra) = itr.next() {
+            return Err(ParseError::at(s, extra, "end of line"));
+        }
+        Ok(Self{
+            from: from .. from + len,
+            to,
+        })
+    }
+}
+
+impl SeedMapEntry {
+    fn map(&self, id: u64) -> Option<u64> {
+        if self.from.contains(&id) {
+            Some(self.to + (id - self.from.start))
+        }
+        else {
+            None
+        }
+    }
+    fn len(&self) -> u64 {
+        self.from.end - self.from.start
+    }
+}
+
+/// The seeds and the maps between categories.
+pub struct Input {
+    seeds: Vec<u64>,
+    seeds_ranges: Vec<Range<u64>>,
+    path: HashMap<String,String>,
+    maps: HashMap<String,Vec<SeedMapEntry>>,
+}
+
+impl Input {
+    fn from_input(input: Vec<Vec<String>>) -> Result<Self, ParseError> {
+        let seeds_line = input.first()
+            .and_then(|g| g.first())
+            .ok_or_else(|| ParseError::missing("", "`seeds:` line").with_line(1))?;
+        let seeds: Vec<u64> = seeds_line.split(' ').skip(1)
+            .map(|s| parse_token(seeds_line, s, "a seed number").map_err(|e| e.with_line(1)))
+            .collect::<Result<_, _>>()?;
+        if seeds.is_empty() {
+            return Err(ParseError::missing(seeds_line, "a seed number").with_line(1));
+        }
+        let seeds_ranges = seeds.iter()
+            .tuples()
+            .map(|(&f, &c)| f .. f+c)
+            .collect();
+        let mut path = HashMap::new();
+        let mut maps = HashMap::new();
+        // each group is followed by exactly one blank line
+        let mut line_num = input[0].len() + 2;
+        for sect in input.iter().skip(1) {
+            if let Some(header) = sect.first() {
+                let name = header.split(' ').next().unwrap();
+                let (frm, to) = name.split_once("-to-")
+                    .ok_or_else(|| ParseError::at(header, name, "`<from>-to-<to> map:`").with_line(line_num))?;
+                path.insert(frm.into(), to.into());
+                let mut v:Vec<SeedMapEntry> = sect.iter()
+                    .enumerate()
+                    .skip(1)
+                    .map(|(idx, s)| s.parse::<SeedMapEntry>().map_err(|e| e.with_line(line_num + idx)))
+                    .collect::<Result<_, _>>()?;
+                v.sort_by_key(|r| r.from.start);
+                maps.insert(frm.into(), v);
+            }
+            line_num += sect.len() + 1;
+        }
+        // the maps have to lead from seeds to locations, which the end of
+        // the almanac is the place to say is missing
+        let last_line = input.last().and_then(|g| g.last()).map_or("", String::as_str);
+        let mut current = "seed";
+        for _ in 0..=path.len() {
+            if current == "location" {
+                return Ok(Self { seeds, seeds_ranges, path, maps });
+            }
+            current = path.get(current).ok_or_else(|| {
+                ParseError::missing(last_line, &format!("a `{current}-to-<to> map:`")).with_line(line_num - 2)
+            })?;
+        }
+        Err(ParseError::missing(last_line, "maps from `seed` that end at `location`").with_line(line_num - 2))
+    }
+
+    fn map_item(&self, frm: &str, to: &str, frm_id: u64) -> u64 {
+        let mut id = frm_id;
+        let mut current = frm;
+        while current != to {
+            let next = &self.path[current];
+            let map = &self.maps[current];
+            if let Some(next_id) = map.iter().find_map(|m| m.map(id)) {
+                id = next_id;
+            }
+            current = next;
+        }
+        id
+    }
+
+    fn map_range(&self, frm: &str, to: &str, range: Range<u64>) -> Vec<Range<u64>> {
+        let mut cur_range = VecDeque::new();
+        cur_range.push_back(range);
+        let mut current = frm;
+        while current != to {
+            let next = &self.path[current];
+            let map = &self.maps[current];
+            let mut next_range = Vec::new();
+            while let Some(r) = cur_range.pop_front() {
+                if let Some(mtch) = map.iter().find(|m| m.from.contains(&r.start)) {
+                    let to_start = mtch.to + (r.start - mtch.from.start);
+                    let len = (r.end - r.start).min(mtch.len() - (to_start - mtch.to));
+                    next_range.push(to_start .. to_start + len);
+                    if len < r.end - r.start {
+                        cur_range.push_front(r.start + len .. r.end);
+                    }
+                }
+                else if let Some(nxt) = map.iter().find(|m| m.from.start > r.start) {
+                    next_range.push(r.start .. r.end.min(nxt.from.start));
+                    if r.end > nxt.from.start {
+                        cur_range.push_front(nxt.from.start .. r.end);
+                    }
+                }
+                else {
+                    next_range.push(r);
+                }
+            }
+            current = next;
+            next_range.sort_by_key(|r| r.start);
+            cur_range = next_range.into();
+        }
+        cur_range.into()
+    }
+
+    /// Reference for `map_range`: maps the ids one at a time.
+    fn map_range_reference(&self, frm: &str, to: &str, range: Range<u64>) -> Vec<Range<u64>> {
+        merge_ranges(range.map(|id| self.map_item(frm, to, id)).map(|id| id .. id + 1).collect())
+    }
+}
+
+/// Sorts `ranges`, and joins any that overlap or touch.
+fn merge_ranges(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    ranges.sort_by_key(|r| r.start);
+    let mut merged: Vec<Range<u64>> = Vec::new();
+    for r in ranges {
+        match merged.last_mut() {
+            Some(last) if r.start <= last.end => { last.end = last.end.max(r.end); },
+            _ => { merged.push(r); },
+        }
+    }
+    merged
+}
+
+/// The location ranges each seed range maps to.
+fn snapshot(input: &Input) -> String {
+    input.seeds_ranges.iter()
+        .map(|sr| format!("{sr:?} -> {:?}\n", input.map_range("seed", "location", sr.clone())))
+        .collect()
+}
+
+/// Lowest location for any of the seeds.
+pub fn part1(input: &Input) -> u64 {
+    input.seeds.iter()
+        .map(|s| input.map_item("seed", "location", *s))
+        .min()
+        .expect("parse found a seed")
+}
+
+/// Lowest location for any seed in the seed ranges. None if the ranges
+/// are all empty.
+pub fn part2(input: &Input, ctx: &Context) -> Option<u64> {
+    let map_range = if ctx.reference { Input::map_range_reference } else { Input::map_range };
+    input.seeds_ranges.iter()
+        .cloned()
+        .map(|sr| map_range(input, "seed", "location", sr))
+        .filter_map(|v| v.first().map(|r| r.start))
+        .min()
+}
+
+/// An almanac with five seed ranges and `ranges` entries in each map. The
+/// numbers are kept small enough (a thousand per entry) that the seeds
+/// can be mapped one at a time.
+fn generate(rng: &mut StdRng, ranges: usize) -> Vec<String> {
+    let names = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+    let span = ranges.max(1) as u64 * 1000;
+    let seeds = (0..10)
+        .map(|i| if i % 2 == 0 { rng.gen_range(0..span) } else { rng.gen_range(1..span / 16) })
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>();
+    let mut lines = vec![format!("seeds: {}", seeds.join(" ")), String::new()];
+    for (from, to) in names.iter().tuple_windows() {
+        lines.push(format!("{from}-to-{to} map:"));
+        let mut cuts: Vec<u64> = (0..ranges * 2).map(|_| rng.gen_range(0..span)).collect();
+        cuts.sort();
+        cuts.dedup();
+        for (start, end) in cuts.into_iter().tuples() {
+            lines.push(format!("{} {start} {}", rng.gen_range(0..span), end - start));
+        }
+        lines.push(String::new());
+    }
+    lines.pop();
+    lines
+}
+
+pub struct Day05;
+
+impl Day for Day05 {
+    const DAY: u32 = 5;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day05.testinput")];
+    type Input = Input;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        Input::from_input(parse_grouped(lines)?)
+    }
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part1(input).into()
+    }
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part2(input, ctx).into()
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(30)))
+    }
+    fn snapshot(&self, input: &Self::Input, _ctx: &Context) -> Option<String> {
+        Some(snapshot(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::grouped_test_input;
+    use crate::gen;
+
+    #[test]
+    fn day05_test() {
+        let input = Input::from_input(grouped_test_input(include_str!("day05.testinput"))).unwrap();
+        assert_eq!(part1(&input), 35);
+        assert_eq!(part2(&input, &Context::default()), Some(46));
+
+        let lines = |s: &str| grouped_test_input::<String>(s);
+        let err = Input::from_input(lines("seeds:\n\nseed-to-location map:\n1 2 3\n")).err().unwrap();
+        assert_eq!(err.to_string(), "line 1, column 7: expected a seed number, found end of line");
+        let err = Input::from_input(lines("seeds: 1 2\n\nseed-to-soil map:\n1 2 3\n")).err().unwrap();
+        assert_eq!(err.to_string(), "line 4, column 6: expected a `soil-to-<to> map:`, found end of line");
+        let err = Input::from_input(lines("seeds: 1 2\n\nseed-to-soil map:\n1 2 3\n\nsoil-to-seed map:\n")).err().unwrap();
+        assert_eq!(err.to_string(), "line 6, column 18: expected maps from `seed` that end at `location`, found end of line");
+        let input = Input::from_input(lines("seeds: 7\n\nseed-to-location map:\n1 2 3\n")).unwrap();
+        assert_eq!((part1(&input), part2(&input, &Context::default())), (7, None));
+    }
+
+    #[test]
+    fn day05_reference_test() {
+        for seed in 0..20 {
+            let lines = generate(&mut gen::rng(seed), 8);
+            let input = Input::from_input(parse_grouped(lines).unwrap()).unwrap();
+            for sr in &input.seeds_ranges {
+                assert_eq!(
+                    merge_ranges(input.map_range("seed", "location", sr.clone())),
+                    input.map_range_reference("seed", "location", sr.clone()),
+                    "seed {seed}, range {sr:?}",
+                );
+            }
+        }
+    }
+}
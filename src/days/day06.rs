@@ -0,0 +1,108 @@
+//! Day 6: Wait For It
+
+use std::vec::Vec;
+use rand::Rng;
+use crate::gen::StdRng;
+use crate::parse::{ParseError, parse_token};
+use crate::runner::{Answer, Context, Day};
+
+/// The race records: each race's time and distance, and the one long
+/// race they make with the spaces taken out, if that fits in a `u64`.
+pub struct Races {
+    times: Vec<u64>,
+    distances: Vec<u64>,
+    time: Option<u64>,
+    distance: Option<u64>,
+}
+
+/// The numbers on the `label` line `s`, separately and run together.
+fn parse_row(s: &str, label: &str) -> Result<(Vec<u64>, Option<u64>), ParseError> {
+    let (l, rest) = s.split_once(':').ok_or_else(|| ParseError::missing(s, "`:`"))?;
+    if l != label {
+        return Err(ParseError::at(s, l, label));
+    }
+    let numbers = rest.split_whitespace()
+        .map(|n| parse_token(s, n, "a number"))
+        .collect::<Result<_, _>>()?;
+    let joined: String = rest.split_whitespace().collect();
+    Ok((numbers, joined.parse().ok()))
+}
+
+impl Races {
+    pub fn from_input(lines: &[String]) -> Result<Self, ParseError> {
+        let row = |idx: usize, label: &str| match lines.get(idx) {
+            Some(s) => parse_row(s, label).map_err(|e| e.with_line(idx + 1)),
+            None => Err(ParseError::missing("", &format!("a `{label}:` line")).with_line(idx + 1)),
+        };
+        let (times, time) = row(0, "Time")?;
+        let (distances, distance) = row(1, "Distance")?;
+        if times.len() != distances.len() {
+            return Err(ParseError::at(&lines[1], &lines[1], &format!("{} distances, one for each time", times.len())).with_line(2));
+        }
+        Ok(Races { times, distances, time, distance })
+    }
+}
+
+fn ways_to_win(t: u64, d: u64) -> usize {
+    (1..t).map(|tt| (t-tt)*tt).filter(|&tt| tt > d).count()
+}
+
+/// Product of the numbers of ways to win each race.
+pub fn part1(races: &Races) -> usize {
+    races.times.iter()
+        .zip(&races.distances)
+        .map(|(t, d)| ways_to_win(*t, *d))
+        .product()
+}
+
+/// Number of ways to win the one long race the kerning hides. None if
+/// its numbers are too long.
+pub fn part2(races: &Races) -> Option<usize> {
+    Some(ways_to_win(races.time?, races.distance?))
+}
+
+/// `races` races. Part 2 joins up the digits, so this gets slow quickly.
+fn generate(rng: &mut StdRng, races: usize) -> Vec<String> {
+    let (times, dists): (Vec<u64>, Vec<u64>) = (0..races)
+        .map(|_| {
+            let t = rng.gen_range(7..100);
+            (t, rng.gen_range(1..(t / 2) * (t - t / 2)))
+        })
+        .unzip();
+    let join = |v: Vec<u64>| v.iter().map(|n| format!("{n:>5}")).collect::<String>();
+    vec![format!("Time:    {}", join(times)), format!("Distance:{}", join(dists))]
+}
+
+pub struct Day06;
+
+impl Day for Day06 {
+    const DAY: u32 = 6;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day06.testinput")];
+    type Input = Races;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        Races::from_input(&lines)
+    }
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part1(input).into()
+    }
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part2(input).into()
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(4)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day06_test() {
+        let input: Vec<String> = test_input(include_str!("day06.testinput"));
+        let races = Races::from_input(&input).unwrap();
+        assert_eq!(part1(&races), 288);
+        assert_eq!(part2(&races), Some(71503));
+    }
+}
@@ -0,0 +1,276 @@
+//! Day 8: Haunted Wasteland
+
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::vec::Vec;
+use lazy_static::lazy_static;
+use num::integer::lcm;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use regex::Regex;
+use crate::budget::OutOfBudget;
+use crate::gen::{self, StdRng};
+use crate::graph::Graph;
+use crate::parse::{ParseError, parse_sectioned};
+use crate::runner::{Answer, Assumption, Context, Day};
+
+/// One node of the network and where left and right lead.
+#[derive(Debug)]
+pub struct Input {
+    id: String,
+    l: String,
+    r: String,
+}
+
+lazy_static! {
+    static ref RE: Regex = Regex::new(
+        r"(\w+) = .(\w+), (\w+)"
+    ).unwrap();
+}
+
+impl FromStr for Input {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(caps) = RE.captures(s) {
+            let id:String = caps.get(1).unwrap().as_str().into();
+            let l:String = caps.get(2).unwrap().as_str().into();
+            let r:String = caps.get(3).unwrap().as_str().into();
+            Ok(Input {id, l, r})
+        }
+        else {
+            Err(ParseError::at(s, s, "`NODE = (LEFT, RIGHT)`"))
+        }
+    }
+}
+
+/// Checks that every node leads to nodes that are defined. `lines` are
+/// the nodes' lines, and `first_line` the number of the first.
+fn check_nodes(input: &[Input], lines: &[String], first_line: usize) -> Result<(), ParseError> {
+    let ids: HashSet<&str> = input.iter().map(|i| i.id.as_str()).collect();
+    for (idx, s) in lines.iter().enumerate() {
+        let caps = RE.captures(s).expect("the node parsed");
+        if let Some(m) = [caps.get(2), caps.get(3)].into_iter().flatten().find(|m| !ids.contains(m.as_str())) {
+            return Err(ParseError::at(s, m.as_str(), "a node that's defined").with_line(first_line + idx));
+        }
+    }
+    Ok(())
+}
+
+/// The network, with an edge labelled `L` or `R` for each way out of a
+/// node.
+fn mkgraph(input: &[Input]) -> Graph<String, char> {
+    let mut graph = Graph::directed();
+    for i in input {
+        graph.add_edge(i.id.clone(), i.l.clone(), 'L');
+        graph.add_edge(i.id.clone(), i.r.clone(), 'R');
+    }
+    graph
+}
+
+/// The network, with start nodes boxed and end nodes circled twice.
+fn dot(input: &[Input]) -> String {
+    mkgraph(input).to_dot_with(
+        |n| if n.ends_with('A') {
+            "shape=box".into()
+        } else if n.ends_with('Z') {
+            "shape=doublecircle".into()
+        } else {
+            String::new()
+        },
+        |_, _, t| format!("label={t}"),
+    )
+}
+
+fn step<'a>(graph: &'a Graph<String, char>, node: &String, turn: char) -> &'a String {
+    graph.neighbors(node)
+        .find(|(_, t)| **t == turn)
+        .map(|(n, _)| n)
+        .unwrap()
+}
+
+/// Steps from `AAA` to `ZZZ`. None if there is no `AAA`.
+pub fn part1(turns: &str, input: &[Input], ctx: &Context) -> Result<Option<usize>, OutOfBudget> {
+    let graph = mkgraph(input);
+    let mut steps = 0;
+    let mut current = &"AAA".to_string();
+    if !graph.contains_node(current) {
+        return Ok(None);
+    }
+    let target = "ZZZ";
+    let mut diriter = turns.chars().cycle();
+    let mut meter = ctx.budget.start("path to ZZZ", 10_000_000);
+    while current != target {
+        meter.tick()?;
+        current = step(&graph, current, diriter.next().unwrap());
+        steps += 1;
+    }
+    Ok(Some(steps))
+}
+
+/// Steps until every ghost starting on an `..A` node is on a `..Z` node.
+/// None if there are no ghosts.
+pub fn part2(turns: &str, input: &[Input], ctx: &Context) -> Result<Option<usize>, OutOfBudget> {
+    let graph = mkgraph(input);
+    let mut steps = 0;
+    let initial = graph.nodes().filter(|n| n.ends_with('A')).collect::<Vec<_>>();
+    let mut currents = initial.clone();
+    let mut diriter = turns.chars().cycle();
+    let mut cycles = vec![0; initial.len()];
+
+    let mut meter = ctx.budget.start("`..Z` node for every ghost", 10_000_000);
+    while cycles.contains(&0) {
+        meter.tick()?;
+        steps += 1;
+        let dir = diriter.next().unwrap();
+        for c in currents.iter_mut() {
+            *c = step(&graph, c, dir);
+        }
+        for i in 0..currents.len() {
+            if cycles[i] == 0 && currents[i].ends_with('Z') {
+                cycles[i] = steps;
+            }
+        }
+    }
+    Ok(cycles.into_iter().reduce(lcm))
+}
+
+/// What part 2 relies on: each ghost gets back to its first `..Z` node
+/// after as many steps again, so the LCM of those counts is when they
+/// all line up.
+fn assumptions(turns: &str, input: &[Input], ctx: &Context) -> Vec<Assumption> {
+    let graph = mkgraph(input);
+    let check = || {
+        for start in graph.nodes().filter(|n| n.ends_with('A')) {
+            let mut meter = ctx.budget.start("ghost's `..Z` nodes", 10_000_000);
+            let mut current = start;
+            let mut diriter = turns.chars().cycle();
+            let mut hits = Vec::new();
+            let mut steps = 0;
+            while hits.len() < 2 {
+                meter.tick().map_err(|e| format!("{start}: {e}"))?;
+                current = step(&graph, current, diriter.next().unwrap());
+                steps += 1;
+                if current.ends_with('Z') {
+                    hits.push((steps, current));
+                }
+            }
+            let ((first, z1), (second, z2)) = (hits[0], hits[1]);
+            if z1 != z2 || second - first != first {
+                return Err(format!("{start} reaches {z1} in {first} steps, then {z2} in {} more", second - first));
+            }
+        }
+        Ok(())
+    };
+    vec![Assumption::check("each ghost gets back to its first `..Z` node in as many steps again", check())]
+}
+
+/// Turns, and six ghost paths: each ghost reaches its `Z` node after a
+/// different prime number of passes through the turns, then loops back
+/// around to it (as the real input does). `turns` is the number of turns;
+/// there are only enough node names for about 300.
+fn generate(rng: &mut StdRng, turns: usize) -> Vec<String> {
+    let inner: Vec<u8> = (b'B'..=b'Y').collect();
+    let dirs: String = (0..turns).map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' }).collect();
+    let mut primes = [2, 3, 5, 7, 11, 13];
+    primes.shuffle(rng);
+    let mut names = gen::names(rng, turns * primes.iter().sum::<usize>(), 3, &inner).into_iter();
+    let prefixes = gen::names(rng, 12, 2, &inner);
+    let mut nodes = Vec::new();
+    for (i, p) in primes.iter().enumerate() {
+        let (start, end) = match i {
+            0 => ("AAA".into(), "ZZZ".into()),
+            _ => (prefixes[i].clone() + "A", prefixes[i + 6].clone() + "Z"),
+        };
+        // node j is always reached on step j of a pass through the turns
+        let len = turns * p;
+        let mut path = vec![start];
+        path.extend(names.by_ref().take(len - 1));
+        path.push(end);
+        for (j, id) in path.iter().enumerate() {
+            let next = &path[if j == len { 1 } else { j + 1 }];
+            let other = &path[rng.gen_range(0..path.len())];
+            let (l, r) = if dirs.as_bytes()[j % turns] == b'L' { (next, other) } else { (other, next) };
+            nodes.push(format!("{id} = ({l}, {r})"));
+        }
+    }
+    nodes.shuffle(rng);
+    [vec![dirs, String::new()], nodes].concat()
+}
+
+pub struct Day08;
+
+impl Day for Day08 {
+    const DAY: u32 = 8;
+    type Input = (Vec<String>, Vec<Input>);
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        let node_lines: Vec<String> = lines.iter()
+            .skip_while(|l| !l.trim_end().is_empty())
+            .skip(1)
+            .cloned()
+            .collect();
+        let (turns, nodes): (Vec<String>, Vec<Input>) = parse_sectioned(lines)?;
+        let t = turns.first()
+            .ok_or_else(|| ParseError::missing("", "a line of turns").with_line(1))?;
+        if t.is_empty() {
+            return Err(ParseError::missing(t, "`L` or `R`").with_line(1));
+        }
+        if let Some((idx, c)) = t.char_indices().find(|(_, c)| !matches!(c, 'L' | 'R')) {
+            return Err(ParseError::at(t, &t[idx .. idx + c.len_utf8()], "`L` or `R`").with_line(1));
+        }
+        check_nodes(&nodes, &node_lines, turns.len() + 2)?;
+        Ok((turns, nodes))
+    }
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part1(&input.0[0], &input.1, ctx).into()
+    }
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part2(&input.0[0], &input.1, ctx).into()
+    }
+    fn dot(&self, input: &Self::Input, _ctx: &Context) -> Option<Result<String, OutOfBudget>> {
+        Some(Ok(dot(&input.1)))
+    }
+    fn assumptions(&self, input: &Self::Input, ctx: &Context) -> Vec<Assumption> {
+        assumptions(&input.0[0], &input.1, ctx)
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(20)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::sectioned_test_input;
+
+    #[test]
+    fn day08_test() {
+        let (turns, input): (Vec<String>, Vec<Input>) = sectioned_test_input(
+"RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)
+");
+        assert_eq!(part1(&turns[0], &input, &Context::default()), Ok(Some(2)));
+        let (turns, input): (Vec<String>, Vec<Input>) = sectioned_test_input(
+"LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+");
+        assert_eq!(part2(&turns[0], &input, &Context::default()), Ok(Some(6)));
+
+        let err = Day08.parse("LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, CCC)\n".lines().map(String::from).collect()).err().unwrap();
+        assert_eq!(err.to_string(), "line 3, column 8: expected a node that's defined, found `BBB`");
+    }
+}
@@ -0,0 +1,107 @@
+//! Day 9: Mirage Maintenance
+
+use std::str::FromStr;
+use std::vec::Vec;
+use itertools::Itertools;
+use rand::Rng;
+use crate::gen::StdRng;
+use crate::parse::{ParseError, parse_lines, parse_token};
+use crate::runner::{Answer, Context, Day};
+
+/// One history: a sequence of values.
+pub struct ValSeq {
+    vals: Vec<i64>,
+}
+
+impl FromStr for ValSeq {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let vals = s.split(' ')
+            .map(|n| parse_token(s, n, "a number"))
+            .collect::<Result<_, _>>()?;
+        Ok(ValSeq{vals})
+    }
+}
+
+impl ValSeq {
+    fn next_prev_val(&self) -> (i64, i64) {
+        let vals: Vec<i64> = self.vals.iter()
+            .tuple_windows()
+            .map(|(a,b)| b - a)
+            .collect();
+        if vals.iter().all(|v| *v == 0) {
+            return (
+                self.vals[0],
+                self.vals[self.vals.len() - 1],
+            );
+        }
+        let (pv, nv) = ValSeq { vals }.next_prev_val();
+        (
+            self.vals[0] - pv,
+            nv + self.vals[self.vals.len() - 1],
+        )
+    }
+}
+
+fn bothparts(input: &[ValSeq]) -> (i64, i64) {
+    input.iter()
+        .map(|i| i.next_prev_val())
+        .reduce(|a,b| (a.0+b.0, a.1+b.1))
+        .unwrap()
+}
+
+/// Sum of the next value of each sequence.
+pub fn part1(input: &[ValSeq]) -> i64 {
+    bothparts(input).0
+}
+
+/// Sum of the value before each sequence.
+pub fn part2(input: &[ValSeq]) -> i64 {
+    bothparts(input).1
+}
+
+/// Sequences of 21 values of random polynomials of degree up to five.
+fn generate(rng: &mut StdRng, lines: usize) -> Vec<String> {
+    (0..lines)
+        .map(|_| {
+            let coeffs: Vec<i64> = (0..rng.gen_range(1..=6)).map(|_| rng.gen_range(-5..=5)).collect();
+            (0..21i64)
+                .map(|x| coeffs.iter().rev().fold(0, |acc, c| acc * x + c).to_string())
+                .join(" ")
+        })
+        .collect()
+}
+
+pub struct Day09;
+
+impl Day for Day09 {
+    const DAY: u32 = 9;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day09.testinput")];
+    type Input = Vec<ValSeq>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        parse_lines(lines)
+    }
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part2(input).into()
+    }
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part1(input).into()
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(200)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day09_test() {
+        let input: Vec<ValSeq> = test_input(include_str!("day09.testinput"));
+        let (part2, part1) = bothparts(&input);
+        assert_eq!(part1, 114);
+        assert_eq!(part2, 2);
+    }
+}
@@ -0,0 +1,346 @@
+//! Day 10: Pipe Maze
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::vec::Vec;
+use rand::Rng;
+use ya_advent_lib::coords::Coord2D;
+use ya_advent_lib::grid::Grid;
+use crate::gen::{self, StdRng};
+use crate::parse::{self, ParseError, check_grid, find_one};
+use crate::render::{Image, Rgb};
+use crate::runner::{Answer, Context, Day};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Cell {
+    Ground,
+    Start,
+    PipeNS,
+    PipeEW,
+    PipeNE,
+    PipeNW,
+    PipeSE,
+    PipeSW,
+}
+
+impl TryFrom<char> for Cell {
+    type Error = char;
+    fn try_from(value: char) -> Result<Self, char> {
+        Ok(match value {
+            '|' => Cell::PipeNS,
+            '-' => Cell::PipeEW,
+            'L' => Cell::PipeNE,
+            'J' => Cell::PipeNW,
+            'F' => Cell::PipeSE,
+            '7' => Cell::PipeSW,
+            'S' => Cell::Start,
+            '.' => Cell::Ground,
+            _ => { return Err(value); },
+        })
+    }
+}
+
+impl Cell {
+    /// The directions a pipe leads in.
+    fn exits(self) -> Vec<Coord2D> {
+        match self {
+            Cell::PipeNS => vec![Coord2D::new(0, -1), Coord2D::new(0, 1)],
+            Cell::PipeEW => vec![Coord2D::new(1, 0), Coord2D::new(-1, 0)],
+            Cell::PipeNE => vec![Coord2D::new(0, -1), Coord2D::new(1, 0)],
+            Cell::PipeNW => vec![Coord2D::new(0, -1), Coord2D::new(-1, 0)],
+            Cell::PipeSE => vec![Coord2D::new(0, 1), Coord2D::new(1, 0)],
+            Cell::PipeSW => vec![Coord2D::new(0, 1), Coord2D::new(-1, 0)],
+            Cell::Ground | Cell::Start => Vec::new(),
+        }
+    }
+}
+
+/// The field of pipes, with the start replaced by the pipe it must be,
+/// and the loop through it.
+pub struct PipeMap {
+    grid: Grid<Cell>,
+    //start_pos: Coord2D,
+    path: HashMap<Coord2D, usize>,
+}
+
+impl PipeMap {
+    /// The map, checking that it has one start, with a loop through it.
+    pub fn from_input(input: &[String]) -> Result<Self, ParseError> {
+        check_grid::<Cell>(input, "one of `|`, `-`, `L`, `J`, `F`, `7`, `S` or `.`")?;
+        let (x, y) = find_one(input, 'S', "exactly one start `S`")?;
+        let start_pos = Coord2D::new(x, y);
+        let mut grid = parse::grid(input, Cell::Ground, 1);
+        // the location in `input` of the pipe at `pos`
+        let error_at = |pos: Coord2D, expected: &str| {
+            let s = &input[pos.y as usize];
+            ParseError::at(s, &s[pos.x as usize ..= pos.x as usize], expected).with_line(pos.y as usize + 1)
+        };
+
+        let conn_n = matches!(
+            grid.get_c(start_pos + Coord2D::new(0, -1)),
+            Cell::PipeNS|Cell::PipeSE|Cell::PipeSW,
+        );
+        let conn_s = matches!(
+            grid.get_c(start_pos + Coord2D::new(0, 1)),
+            Cell::PipeNS|Cell::PipeNE|Cell::PipeNW,
+        );
+        let conn_e = matches!(
+            grid.get_c(start_pos + Coord2D::new(1, 0)),
+            Cell::PipeEW|Cell::PipeNW|Cell::PipeSW,
+        );
+        let conn_w = matches!(
+            grid.get_c(start_pos + Coord2D::new(-1, 0)),
+            Cell::PipeEW|Cell::PipeNE|Cell::PipeSE,
+        );
+        let replace = match (conn_n, conn_s, conn_e, conn_w) {
+            (true, true, false, false) => Cell::PipeNS,
+            (false, false, true, true) => Cell::PipeEW,
+            (true, false, true, false) => Cell::PipeNE,
+            (true, false, false, true) => Cell::PipeNW,
+            (false, true, true, false) => Cell::PipeSE,
+            (false, true, false, true) => Cell::PipeSW,
+            _ => { return Err(error_at(start_pos, "a start with two pipes leading into it")); },
+        };
+        grid.set_c(start_pos, replace);
+        let path = find_path(&grid, start_pos)
+            .map_err(|pos| error_at(pos, "a pipe leading to another one on the loop"))?;
+        Ok(Self { grid, path })
+    }
+
+    /// Cells on the loop, with their distance along it from the start.
+    pub fn path(&self) -> &HashMap<Coord2D, usize> {
+        &self.path
+    }
+
+    /// Tiles enclosed by the loop.
+    pub fn find_interior(&self) -> HashSet<Coord2D> {
+        let mut interior: HashSet<Coord2D> = HashSet::new();
+        for y in self.grid.y_bounds() {
+            let mut inside = false;
+            for x in self.grid.x_bounds() {
+                let loc = Coord2D::new(x, y);
+                if self.path.contains_key(&loc) {
+                    match self.grid.get_c(loc) {
+                        Cell::PipeNS | Cell::PipeNE | Cell::PipeNW => { inside = !inside; },
+                        _ => {},
+                    }
+                }
+                else if inside {
+                    interior.insert(loc);
+                }
+            }
+        }
+        interior
+    }
+}
+
+/// The loop through `start_pos`, or the first pipe found on it that
+/// leads somewhere that doesn't lead back.
+fn find_path(grid: &Grid<Cell>, start_pos: Coord2D) -> Result<HashMap<Coord2D, usize>, Coord2D> {
+    let mut path: HashMap<Coord2D,usize> = HashMap::new();
+    let mut queue: VecDeque<Coord2D> = VecDeque::new();
+    queue.push_back(start_pos);
+    path.insert(start_pos, 0);
+    while let Some(c) = queue.pop_front() {
+        let n = path[&c] + 1;
+        for next in grid.get_c(c).exits() {
+            let nextc = c + next;
+            if !grid.get_c(nextc).exits().contains(&Coord2D::new(-next.x, -next.y)) {
+                return Err(c);
+            }
+            if !path.contains_key(&nextc) || path[&nextc] > n {
+                path.insert(nextc, n);
+                queue.push_back(nextc);
+            }
+        }
+    }
+    Ok(path)
+}
+
+/// Steps along the loop to the point farthest from the start.
+pub fn part1(map: &PipeMap) -> usize {
+    map.path.values().copied().max().unwrap()
+}
+
+/// Number of tiles the loop encloses.
+pub fn part2(map: &PipeMap) -> usize {
+    let interior = map.find_interior();
+    interior.len()
+}
+
+/// Each loop cell's distance from the start, on the map.
+fn snapshot(map: &PipeMap) -> String {
+    let width = map.path.values().max().map_or(1, |n| n.to_string().len());
+    map.grid.y_bounds_orig()
+        .map(|y| map.grid.x_bounds_orig()
+            .map(|x| map.path.get(&Coord2D::new(x, y)).map_or(".".into(), |n| n.to_string()))
+            .map(|s| format!("{s:>width$}"))
+            .collect::<Vec<_>>()
+            .join(" ") + "\n")
+        .collect()
+}
+
+/// The loop in yellow and the cells it encloses in green.
+fn render(map: &PipeMap) -> Image {
+    let mut image = Image::from_grid(&map.grid, |c| match c {
+        Cell::Ground => Rgb::BLACK,
+        _ => Rgb::DARK_GRAY,
+    });
+    image.overlay(map.path.keys().copied(), Rgb::YELLOW);
+    image.overlay(map.find_interior(), Rgb::GREEN);
+    image
+}
+
+/// A `size` by `size` field with a random loop through it, and stray
+/// pipes all around.
+fn generate(rng: &mut StdRng, size: usize) -> Vec<String> {
+    let size = size.max(6) as i64;
+    let moves = gen::skyline(rng, (size as usize - 2) / 4, 4, size - 3);
+    let mut conns: HashMap<Coord2D, Vec<char>> = HashMap::new();
+    let mut pos = Coord2D::new(1, size - 2);
+    for (dir, len) in moves {
+        let (step, back) = match dir {
+            'U' => (Coord2D::new(0, -1), 'D'),
+            'D' => (Coord2D::new(0, 1), 'U'),
+            'L' => (Coord2D::new(-1, 0), 'R'),
+            _ => (Coord2D::new(1, 0), 'L'),
+        };
+        for _ in 0..len {
+            conns.entry(pos).or_default().push(dir);
+            pos += step;
+            conns.entry(pos).or_default().push(back);
+        }
+    }
+    let pipe = |dirs: &[char]| match (dirs.contains(&'U'), dirs.contains(&'D'), dirs.contains(&'L')) {
+        (true, true, _) => '|',
+        (true, false, true) => 'J',
+        (true, false, false) => 'L',
+        (false, true, true) => '7',
+        (false, true, false) => 'F',
+        _ => '-',
+    };
+    let start = *conns.keys().nth(rng.gen_range(0..conns.len())).unwrap();
+    let junk = b"|-LJF7";
+    (0..size)
+        .map(|y| (0..size)
+            .map(|x| {
+                let c = Coord2D::new(x, y);
+                if c == start {
+                    'S'
+                }
+                else if let Some(dirs) = conns.get(&c) {
+                    pipe(dirs)
+                }
+                else if c.mdist_to(&start) > 1 && rng.gen_bool(0.4) {
+                    junk[rng.gen_range(0..junk.len())] as char
+                }
+                else {
+                    '.'
+                }
+            })
+            .collect())
+        .collect()
+}
+
+pub struct Day10;
+
+impl Day for Day10 {
+    const DAY: u32 = 10;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day10.testinput")];
+    type Input = PipeMap;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        PipeMap::from_input(&lines)
+    }
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part1(input).into()
+    }
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part2(input).into()
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(140)))
+    }
+    fn render(&self, input: &Self::Input, _ctx: &Context) -> Option<Image> {
+        Some(render(input))
+    }
+    fn snapshot(&self, input: &Self::Input, _ctx: &Context) -> Option<String> {
+        Some(snapshot(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    fn map(s: &str) -> PipeMap {
+        PipeMap::from_input(&test_input::<String>(s)).unwrap()
+    }
+
+    #[test]
+    fn day10_test() {
+        let input = map(
+"-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF
+");
+        assert_eq!(part1(&input), 4);
+        let input = map(
+"7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ
+");
+        assert_eq!(part1(&input), 8);
+
+        let input = map(
+"...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........
+");
+        assert_eq!(part2(&input), 4);
+        let input = map(
+".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...
+");
+        assert_eq!(part2(&input), 8);
+        let input = map(
+"FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+");
+        assert_eq!(part2(&input), 10);
+
+        let input: Vec<String> = test_input(".....\n.S-7.\n.|.|.\n.L-J.\n");
+        assert!(PipeMap::from_input(&input).is_ok());
+        let err = PipeMap::from_input(&input[..3]).err().unwrap();
+        assert_eq!(err.to_string(), "line 3, column 2: expected a pipe leading to another one on the loop, found `|`");
+        let err = PipeMap::from_input(&[input[0].clone(), ".S-..".into()]).err().unwrap();
+        assert_eq!(err.to_string(), "line 2, column 2: expected a start with two pipes leading into it, found `S`");
+        let err = PipeMap::from_input(&input.iter().map(|s| s.replace('S', ".")).collect::<Vec<_>>()).err().unwrap();
+        assert_eq!(err.to_string(), "line 4, column 6: expected exactly one start `S`, found end of line");
+    }
+}
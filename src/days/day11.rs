@@ -0,0 +1,146 @@
+//! Day 11: Cosmic Expansion
+
+use std::collections::{BTreeSet, HashSet};
+use std::vec::Vec;
+use itertools::Itertools;
+use rand::Rng;
+use crate::bignum::Int;
+use crate::gen::{self, StdRng};
+use crate::parse::ParseError;
+use crate::runner::{Answer, Context, Day, Param};
+
+/// The galaxies in an image, and which rows and columns have any.
+pub struct Galaxies {
+    galaxies: HashSet<(i64,i64)>,
+    rows: BTreeSet<i64>,
+    cols: BTreeSet<i64>,
+}
+
+impl Galaxies {
+    pub fn from_input(input: &[String]) -> Self {
+        let galaxies = HashSet::from_iter(
+            input.iter()
+                .enumerate()
+                .flat_map(|(y, s)| s.match_indices('#')
+                    .map(move |(x,_)| (x as i64, y as i64))
+                )
+        );
+        let rows = BTreeSet::from_iter(
+            galaxies.iter()
+            .map(|(_,y)| *y)
+        );
+        let cols = BTreeSet::from_iter(
+            galaxies.iter()
+            .map(|(x,_)| *x)
+        );
+        Self {galaxies, rows, cols}
+    }
+    /// Distance between two galaxies, with each empty row and column
+    /// `exp_fact` times as wide.
+    pub fn md_between(&self, x1: i64, y1: i64, x2: i64, y2: i64, exp_fact: i64) -> Int {
+        let xd = (x2 - x1).abs();
+        let xsp = xd - self.cols.range(x1.min(x2) .. x1.max(x2)).count() as i64;
+        let yd = (y2 - y1).abs();
+        let ysp = yd - self.rows.range(y1.min(y2) .. y1.max(y2)).count() as i64;
+        Int::from(xd + yd) + Int::from(xsp + ysp) * (Int::from(exp_fact) - Int::from(1))
+    }
+    /// Reference for `md_between`: walks from one galaxy to the other a
+    /// row or column at a time, with empty ones counting `exp_fact` steps.
+    fn md_between_reference(&self, x1: i64, y1: i64, x2: i64, y2: i64, exp_fact: i64) -> Int {
+        let width = |occupied: &BTreeSet<i64>, n| Int::from(if occupied.contains(&n) { 1 } else { exp_fact });
+        (x1.min(x2) .. x1.max(x2)).map(|x| width(&self.cols, x))
+            .chain((y1.min(y2) .. y1.max(y2)).map(|y| width(&self.rows, y)))
+            .sum()
+    }
+}
+
+const EXPANSION: Param = Param { name: "expansion", default: 1_000_000, min: 1, step: 1, help: "how many times as wide empty rows and columns are in part 2" };
+
+fn solve(galaxies: &Galaxies, exp_fact: i64, ctx: &Context) -> Int {
+    let md_between = if ctx.reference { Galaxies::md_between_reference } else { Galaxies::md_between };
+    galaxies.galaxies.iter()
+        .tuple_combinations()
+        .map(|(a,b)| md_between(galaxies, a.0, a.1, b.0, b.1, exp_fact))
+        .sum()
+}
+
+/// Sum of the distances between galaxy pairs, with empty rows and columns doubled.
+pub fn part1(galaxies: &Galaxies, ctx: &Context) -> Int {
+    solve(galaxies, 2, ctx)
+}
+
+/// Like part 1, but empty rows and columns are a million times as wide.
+pub fn part2(galaxies: &Galaxies, ctx: &Context) -> Int {
+    solve(galaxies, ctx.param(&EXPANSION), ctx)
+}
+
+/// A `size` by `size` image with about one row and column in ten empty.
+fn generate(rng: &mut StdRng, size: usize) -> Vec<String> {
+    let empty_cols: Vec<bool> = (0..size).map(|_| rng.gen_bool(0.1)).collect();
+    let mut x = 0;
+    gen::grid(rng, size, size, |rng| {
+        let col = x % size;
+        x += 1;
+        if !empty_cols[col] && rng.gen_bool(0.03) { '#' } else { '.' }
+    })
+        .into_iter()
+        .map(|row| if rng.gen_bool(0.1) { ".".repeat(size) } else { row })
+        .collect()
+}
+
+pub struct Day11;
+
+impl Day for Day11 {
+    const DAY: u32 = 11;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day11.testinput")];
+    const PARAMS: &'static [Param] = &[EXPANSION];
+    type Input = Galaxies;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        Ok(Galaxies::from_input(&lines))
+    }
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part1(input, ctx).into()
+    }
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part2(input, ctx).into()
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(140)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day11_test() {
+        let input: Vec<String> = test_input(include_str!("day11.testinput"));
+        let galaxies = Galaxies::from_input(&input);
+        let ctx = Context::default();
+        assert_eq!(part1(&galaxies, &ctx), 374);
+        let ex = solve(&galaxies, 10, &ctx);
+        assert_eq!(ex, 1030);
+        let ex = solve(&galaxies, 100, &ctx);
+        assert_eq!(ex, 8410);
+        // 292 + 82 * (exp_fact - 1), which no longer fits in an i64
+        let ex = solve(&galaxies, i64::MAX, &ctx);
+        assert_eq!(ex, 292 + 82 * (i64::MAX as i128 - 1));
+    }
+
+    #[test]
+    fn day11_reference_test() {
+        let reference = Context { reference: true, ..Context::default() };
+        for seed in 0..20 {
+            let galaxies = Galaxies::from_input(&generate(&mut gen::rng(seed), 30));
+            for exp_fact in [2, 10, 1_000_000] {
+                assert_eq!(
+                    solve(&galaxies, exp_fact, &Context::default()),
+                    solve(&galaxies, exp_fact, &reference),
+                    "seed {seed}, expansion {exp_fact}",
+                );
+            }
+        }
+    }
+}
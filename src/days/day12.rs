@@ -0,0 +1,254 @@
+//! Day 12: Hot Springs
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::vec::Vec;
+use rand::Rng;
+use crate::bignum::Int;
+use crate::budget::OutOfBudget;
+use crate::gen::StdRng;
+use crate::parse::{ParseError, next_token, parse_lines, parse_token};
+use crate::runner::{Answer, Context, Day};
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum State {
+    Good,
+    Bad,
+    Unknown,
+}
+
+/// One row of the records: the springs and the sizes of the damaged groups.
+pub struct Input {
+    springs: Vec<State>,
+    counts: Vec<usize>,
+}
+
+impl FromStr for Input {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut itr = s.split(' ');
+        let springs = next_token(s, &mut itr, "a row of springs")?;
+        let springs = springs.char_indices().map(|(idx, c)| match c {
+            '?' => Ok(State::Unknown),
+            '#' => Ok(State::Good),
+            '.' => Ok(State::Bad),
+            _ => Err(ParseError::at(s, &springs[idx .. idx + c.len_utf8()], "`?`, `#` or `.`")),
+        }).collect::<Result<_, _>>()?;
+        let counts = next_token(s, &mut itr, "group counts")?
+            .split(',')
+            .map(|n| parse_token(s, n, "a group count"))
+            .collect::<Result<_, _>>()?;
+        Ok(Input{ springs, counts })
+    }
+}
+impl Input {
+    /// The row unfolded, as part 2 reads it.
+    pub fn expanded(&self) -> Self {
+        let mut springs = Vec::with_capacity(self.springs.len()*5 + 4);
+        let mut counts = Vec::with_capacity(self.counts.len()*5);
+        for n in 0..5 {
+            springs.extend(self.springs.iter());
+            if n < 4 {
+                springs.push(State::Unknown);
+            }
+            counts.extend(self.counts.iter());
+        }
+        Self { springs, counts }
+    }
+}
+
+/// Counts the arrangements of a row, remembering counts for positions
+/// already seen.
+pub struct Solver<'a> {
+    springs: &'a Vec<State>,
+    counts: &'a Vec<usize>,
+    cache: RefCell<HashMap<(usize, usize, usize), Int>>,
+}
+
+impl<'a> Solver<'a> {
+    pub fn new(input: &'a Input) -> Self {
+        Self {
+            springs: &input.springs,
+            counts: &input.counts,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Number of arrangements of the springs that fit the counts.
+    pub fn solve(&self) -> Int {
+        self.search(0, 0, 0)
+    }
+
+    fn search(&self, spr_idx:usize, cnt_idx:usize, current_cnt:usize) -> Int {
+        let key = (spr_idx, cnt_idx, current_cnt);
+        if let Some(val) = self.cache.borrow().get(&key) {
+            return val.clone();
+        }
+        if spr_idx == self.springs.len() {
+            if cnt_idx == self.counts.len() && current_cnt == 0 {
+                return Int::from(1);
+            }
+            if cnt_idx == self.counts.len() - 1 && self.counts[cnt_idx] == current_cnt {
+                return Int::from(1);
+            }
+            return Int::from(0);
+        }
+        let mut total = Int::from(0);
+        for st in [State::Bad, State::Good] {
+            if self.springs[spr_idx] == st || self.springs[spr_idx] == State::Unknown {
+                if st == State::Bad && current_cnt == 0 {
+                    total = total + self.search(spr_idx + 1, cnt_idx, 0);
+                }
+                else if st == State::Bad &&
+                        current_cnt > 0 &&
+                        cnt_idx < self.counts.len() &&
+                        self.counts[cnt_idx] == current_cnt {
+                    total = total + self.search(spr_idx + 1, cnt_idx + 1, 0);
+                }
+                else if st == State::Good {
+                    total = total + self.search(spr_idx + 1, cnt_idx, current_cnt + 1);
+                }
+            }
+        }
+
+        self.cache.borrow_mut().insert(key, total.clone());
+        total
+    }
+}
+
+/// Reference for `Solver`: tries every way of filling in the unknown
+/// springs, and counts the ones that give the right groups. That's two
+/// ways per unknown spring, so each one is charged to the budget.
+fn arrangements_reference(input: &Input, ctx: &Context) -> Result<usize, OutOfBudget> {
+    let unknown: Vec<usize> = (0..input.springs.len())
+        .filter(|idx| input.springs[*idx] == State::Unknown)
+        .collect();
+    let mut springs = input.springs.clone();
+    // which unknown springs are good, counting up in binary
+    let mut good = vec![false; unknown.len()];
+    let mut count = 0;
+    let mut meter = ctx.budget.start("every arrangement of the unknown springs", 1 << 20);
+    loop {
+        meter.tick()?;
+        for (g, idx) in good.iter().zip(&unknown) {
+            springs[*idx] = if *g { State::Good } else { State::Bad };
+        }
+        let groups: Vec<usize> = springs.split(|s| *s == State::Bad)
+            .map(|g| g.len())
+            .filter(|n| *n > 0)
+            .collect();
+        if groups == *input.counts {
+            count += 1;
+        }
+        match good.iter().position(|g| !g) {
+            Some(n) => {
+                good[..n].fill(false);
+                good[n] = true;
+            },
+            None => { return Ok(count); },
+        }
+    }
+}
+
+fn arrangements(input: &Input, ctx: &Context) -> Result<Int, OutOfBudget> {
+    if ctx.reference {
+        arrangements_reference(input, ctx).map(Int::from)
+    }
+    else {
+        Ok(Solver::new(input).solve())
+    }
+}
+
+/// Sum of the numbers of possible arrangements of each row.
+pub fn part1(input: &[Input], ctx: &Context) -> Result<Int, OutOfBudget> {
+    ctx.map(input, |i| arrangements(i, ctx))
+        .into_iter()
+        .sum()
+}
+
+/// Like part 1, with each row unfolded to five copies.
+pub fn part2(input: &[Input], ctx: &Context) -> Result<Int, OutOfBudget> {
+    ctx.map(input, |i| arrangements(&i.expanded(), ctx))
+        .into_iter()
+        .sum()
+}
+
+/// Rows of up to 20 springs, with about half of them unknown.
+fn generate(rng: &mut StdRng, rows: usize) -> Vec<String> {
+    (0..rows)
+        .map(|_| {
+            let mut springs: String = (0..rng.gen_range(6..=20))
+                .map(|_| if rng.gen_bool(0.5) { '#' } else { '.' })
+                .collect();
+            if !springs.contains('#') {
+                let idx = rng.gen_range(0..springs.len());
+                springs.replace_range(idx..=idx, "#");
+            }
+            let counts = springs.split('.')
+                .filter(|g| !g.is_empty())
+                .map(|g| g.len().to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            let springs: String = springs.chars()
+                .map(|c| if rng.gen_bool(0.5) { '?' } else { c })
+                .collect();
+            format!("{springs} {counts}")
+        })
+        .collect()
+}
+
+pub struct Day12;
+
+impl Day for Day12 {
+    const DAY: u32 = 12;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day12.testinput")];
+    type Input = Vec<Input>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        parse_lines(lines)
+    }
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part1(input, ctx).into()
+    }
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part2(input, ctx).into()
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(1000)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+    use crate::budget::Budget;
+    use crate::gen;
+
+    #[test]
+    fn day12_test() {
+        let input: Vec<Input> = test_input(include_str!("day12.testinput"));
+        for ctx in [Context::default(), Context { parallel: true, ..Context::default() }] {
+            assert_eq!(part1(&input, &ctx), Ok(Int::from(21)));
+            assert_eq!(part2(&input, &ctx), Ok(Int::from(525152)));
+        }
+    }
+
+    #[test]
+    fn day12_reference_test() {
+        for seed in 0..20 {
+            for line in generate(&mut gen::rng(seed), 20) {
+                let input: Input = line.parse().unwrap();
+                assert_eq!(Solver::new(&input).solve(), arrangements_reference(&input, &Context::default()).unwrap(), "{line}");
+                if input.springs.iter().filter(|s| **s == State::Unknown).count() <= 2 {
+                    let input = input.expanded();
+                    assert_eq!(Solver::new(&input).solve(), arrangements_reference(&input, &Context::default()).unwrap(), "{line} expanded");
+                }
+            }
+        }
+        // far too many ways to try them all, for the budget or a u64
+        let input: Input = format!("{} 1", "?".repeat(70)).parse().unwrap();
+        let ctx = Context { reference: true, budget: Budget { iterations: Some(1000), ..Budget::default() }, ..Context::default() };
+        assert_eq!(arrangements(&input, &ctx), Err(OutOfBudget::Iterations { what: "every arrangement of the unknown springs", limit: 1000 }));
+    }
+}
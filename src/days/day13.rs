@@ -0,0 +1,223 @@
+//! Day 13: Point of Incidence
+
+use std::vec::Vec;
+use itertools::Itertools;
+use rand::Rng;
+use ya_advent_lib::coords::Coord2D;
+use ya_advent_lib::grid::Grid;
+use crate::gen::{self, StdRng};
+use crate::parse::{ParseError, parse_grouped};
+use crate::render::{Image, Rgb};
+use crate::runner::{Answer, Context, Day};
+
+fn mkgrid(inp: &[String]) -> Grid<char> {
+    Grid::from_input(inp, '.', 0)
+}
+
+#[derive(Debug)]
+enum Reflection {
+    Horiz(usize),
+    Vert(usize),
+}
+impl Reflection {
+    fn value(&self) -> usize {
+        match self {
+            Reflection::Horiz(n) => 100 * (n+1),
+            Reflection::Vert(n) => n+1,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum SmudgeEq {
+    Equal,
+    Smudged,
+    NoMatch,
+}
+
+fn smudge_eq(a: &[char], b: &[char], check_for_smudge: bool) -> SmudgeEq {
+    assert_eq!(a.len(), b.len());
+    let m = a.iter().zip(b.iter())
+        .filter(|(aa,bb)| *aa == *bb)
+        .count();
+    if m == a.len() {
+        SmudgeEq::Equal
+    }
+    else if check_for_smudge && m == a.len() - 1 {
+        SmudgeEq::Smudged
+    }
+    else {
+        SmudgeEq::NoMatch
+    }
+}
+
+fn check_symmetry(rows: &[Vec<char>], part2: bool) -> Option<usize> {
+    for (smudge, candidate) in rows.iter().enumerate()
+            .tuple_windows()
+            .map(|((idx,r1),(_,r2))| (smudge_eq(r1, r2, part2), idx))
+            .filter(|(sm, _)| *sm != SmudgeEq::NoMatch) {
+        let mut good = true;
+        let mut check_for_smudge = part2 && smudge == SmudgeEq::Equal;
+        for i in 0..candidate {
+            let a = candidate - 1 - i;
+            let b = candidate + 2 + i;
+            if b >= rows.len() {
+                break;
+            }
+            match smudge_eq(&rows[a], &rows[b], check_for_smudge) {
+                SmudgeEq::Equal => {},
+                SmudgeEq::Smudged => {
+                    if check_for_smudge {
+                        check_for_smudge = false;
+                    }
+                    else {
+                        good = false;
+                        break;
+                    }
+                },
+                SmudgeEq::NoMatch => {
+                    good = false;
+                    break;
+                },
+            }
+        }
+        if good && (!part2 || !check_for_smudge) {
+            return Some(candidate);
+        }
+    }
+    None
+}
+
+fn find_reflection(grid: &Grid<char>, part2: bool) -> Option<Reflection> {
+    let rows: Vec<_> = grid.rows().collect();
+    if let Some(n) = check_symmetry(&rows, part2) {
+        return Some(Reflection::Horiz(n));
+    }
+    let rows: Vec<_> = grid.cols().collect();
+    check_symmetry(&rows, part2).map(Reflection::Vert)
+}
+
+/// Summary of the reflection lines of all the patterns. None if a pattern
+/// has no reflection.
+pub fn part1(input: &[Vec<String>]) -> Option<usize> {
+    input.iter()
+        .map(|s| mkgrid(s))
+        .map(|g| find_reflection(&g, false).map(|r| r.value()))
+        .sum()
+}
+
+/// Like part 1, with the line each pattern has after fixing its smudge.
+pub fn part2(input: &[Vec<String>]) -> Option<usize> {
+    input.iter()
+        .map(|s| mkgrid(s))
+        .map(|g| find_reflection(&g, true).map(|r| r.value()))
+        .sum()
+}
+
+/// The patterns stacked top to bottom, with the ash on either side of
+/// the part 1 reflection line in blue and the part 2 line in orange.
+fn render(input: &[Vec<String>]) -> Image {
+    let grids: Vec<Grid<char>> = input.iter().map(|s| mkgrid(s)).collect();
+    let width = grids.iter().map(|g| g.x_bounds().count()).max().unwrap_or(0);
+    let height = grids.iter().map(|g| g.y_bounds().count() + 1).sum::<usize>().saturating_sub(1);
+    let mut image = Image::new(width, height, Rgb::DARK_GRAY);
+    let mut top = 0;
+    for grid in &grids {
+        let mut pattern = Image::from_grid(grid, |c| if c == '#' { Rgb::WHITE } else { Rgb::BLACK });
+        for (part2, color) in [(false, Rgb::BLUE), (true, Rgb::ORANGE)] {
+            let (xb, yb) = (grid.x_bounds(), grid.y_bounds());
+            let line: Vec<Coord2D> = match find_reflection(grid, part2) {
+                Some(Reflection::Horiz(n)) => {
+                    let n = n as i64;
+                    xb.flat_map(|x| [Coord2D::new(x, n), Coord2D::new(x, n + 1)]).collect()
+                },
+                Some(Reflection::Vert(n)) => {
+                    let n = n as i64;
+                    yb.flat_map(|y| [Coord2D::new(n, y), Coord2D::new(n + 1, y)]).collect()
+                },
+                None => Vec::new(),
+            };
+            pattern.overlay(line.into_iter().filter(|c| grid.get_c(*c) == '.'), color);
+        }
+        image.draw(Coord2D::new(0, top), &pattern);
+        top += pattern.height() as i64 + 1;
+    }
+    image
+}
+
+/// A random pattern, mirrored across a random line.
+fn mirrored_pattern(rng: &mut StdRng) -> Vec<String> {
+    let (w, h) = (rng.gen_range(5..=17), rng.gen_range(5..=17));
+    let mut rows: Vec<Vec<char>> = gen::grid(rng, w, h, |rng| if rng.gen_bool(0.5) { '#' } else { '.' })
+        .iter()
+        .map(|r| r.chars().collect())
+        .collect();
+    let vertical = rng.gen_bool(0.5);
+    let line = rng.gen_range(0..if vertical { w } else { h } - 1);
+    for (a, b) in (0..=line).rev().zip(line + 1..) {
+        if vertical && b < w {
+            rows.iter_mut().for_each(|r| r[b] = r[a]);
+        }
+        else if !vertical && b < h {
+            rows[b] = rows[a].clone();
+        }
+    }
+    rows.iter().map(|r| r.iter().collect()).collect()
+}
+
+/// `patterns` patterns, each with a reflection, and another that a smudge
+/// hides.
+fn generate(rng: &mut StdRng, patterns: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for _ in 0..patterns {
+        let pattern = loop {
+            let p = mirrored_pattern(rng);
+            let g = mkgrid(&p);
+            if find_reflection(&g, false).is_some() && find_reflection(&g, true).is_some() {
+                break p;
+            }
+        };
+        lines.extend(pattern);
+        lines.push(String::new());
+    }
+    lines.pop();
+    lines
+}
+
+pub struct Day13;
+
+impl Day for Day13 {
+    const DAY: u32 = 13;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day13.testinput")];
+    type Input = Vec<Vec<String>>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        parse_grouped(lines)
+    }
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part1(input).into()
+    }
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part2(input).into()
+    }
+    fn render(&self, input: &Self::Input, _ctx: &Context) -> Option<Image> {
+        Some(render(input))
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(100)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::grouped_test_input;
+
+    #[test]
+    fn day13_test() {
+        let input: Vec<Vec<String>> = grouped_test_input(include_str!("day13.testinput"));
+        assert_eq!(part1(&input), Some(405));
+        assert_eq!(part2(&input), Some(400));
+        let input = vec![vec!["#.".to_string(), "..".to_string()]];
+        assert_eq!((part1(&input), part2(&input)), (None, Some(100)));
+    }
+}
@@ -0,0 +1,231 @@
+//! Day 14: Parabolic Reflector Dish
+
+use std::collections::HashMap;
+use std::vec::Vec;
+use json::object;
+use rand::Rng;
+use ya_advent_lib::grid::{Grid, GridTransform};
+use ya_advent_lib::coords::CDir;
+use crate::budget::OutOfBudget;
+use crate::gen::{self, StdRng};
+use crate::parse::{self, ParseError, check_grid};
+use crate::render::{Image, Rgb};
+use crate::runner::{Answer, Context, Day, Param};
+
+#[derive(Copy,Clone,Eq,PartialEq)]
+enum Cell {
+    Round,
+    Cube,
+    Empty,
+}
+impl TryFrom<char> for Cell {
+    type Error = char;
+    fn try_from(value: char) -> Result<Self, char> {
+        Ok(match value {
+            '.' => Cell::Empty,
+            'O' => Cell::Round,
+            '#' => Cell::Cube,
+            _ => { return Err(value); },
+        })
+    }
+}
+impl From<Cell> for char {
+    fn from(value: Cell) -> char {
+        match value {
+            Cell::Empty => '.',
+            Cell::Round => 'O',
+            Cell::Cube  => '#',
+        }
+    }
+}
+
+fn mkgrid(input: &[String]) -> Grid<Cell> {
+    parse::grid(input, Cell::Empty, 0)
+}
+
+/// Untransformed coordinates of `(x, y)` in the view `xform` gives of
+/// `grid`, for tracing.
+fn untransform(grid: &Grid<Cell>, x: i64, y: i64, xform: GridTransform) -> [i64; 2] {
+    let xsize = grid.x_bounds().end - grid.x_bounds().start;
+    let ysize = grid.y_bounds().end - grid.y_bounds().start;
+    match xform {
+        GridTransform::Rot90 => [y, ysize - 1 - x],
+        GridTransform::Rot180 => [xsize - 1 - x, ysize - 1 - y],
+        GridTransform::Rot270 => [xsize - 1 - y, x],
+        _ => [x, y],
+    }
+}
+
+fn move_rock(x: i64, y: i64, xform: GridTransform, grid: &mut Grid<Cell>, ctx: &Context) -> bool {
+    let start = y;
+    let mut y = y;
+    while y > 0 && grid.get_xform(x, y - 1, xform) == Cell::Empty {
+        grid.set_xform(x, y, Cell::Empty, xform);
+        grid.set_xform(x, y - 1, Cell::Round, xform);
+        y -= 1;
+    }
+    y == start || ctx.trace("rock_moved", || object!{
+        from: untransform(grid, x, start, xform).as_slice(),
+        to: untransform(grid, x, y, xform).as_slice(),
+    })
+}
+
+/// Tilts the platform. Returns false if the trace stopped it part way.
+fn tilt(grid: &mut Grid<Cell>, dir: CDir, ctx: &Context) -> bool {
+    let xform = match dir {
+        CDir::N => GridTransform::Identity,
+        CDir::W => GridTransform::Rot90,
+        CDir::S => GridTransform::Rot180,
+        CDir::E => GridTransform::Rot270,
+    };
+    let xb = grid.x_bounds_xform(xform);
+    let yb = grid.y_bounds_xform(xform);
+    for y in yb {
+        for x in xb.clone() {
+            if grid.get_xform(x, y, xform) == Cell::Round && !move_rock(x, y, xform, grid, ctx) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+fn weight(grid: &Grid<Cell>) -> i64 {
+    let h = grid.y_bounds().end - grid.y_bounds().start;
+    grid.iter_with_coord()
+        .filter(|(c,_,_)| *c == Cell::Round)
+        .map(|(_,_,y)| h - y)
+        .sum()
+}
+
+/// Load on the north beams after tilting north.
+pub fn part1(input: &[String], ctx: &Context) -> i64 {
+    let mut grid = mkgrid(input);
+    tilt(&mut grid, CDir::N, ctx);
+    //grid.print(|c| c.to_char());
+    weight(&grid)
+}
+
+fn grid_to_str(grid: &Grid<Cell>) -> String {
+    grid.iter().map(|&c| Into::<char>::into(c)).collect()
+}
+fn str_to_grid(grid: &mut Grid<Cell>, s: &str) {
+    let mut chars = s.chars();
+    grid.iter_mut().for_each(|c| *c = Cell::try_from(chars.next().unwrap()).unwrap());
+}
+
+const CYCLES: Param = Param { name: "cycles", default: 1_000_000_000, min: 0, step: 1, help: "spin cycles to run in part 2" };
+
+/// Load on the north beams after a billion spin cycles. Relies on the
+/// rocks settling into a repeating cycle.
+pub fn part2(input: &[String], ctx: &Context) -> Result<i64, OutOfBudget> {
+    let target = ctx.param(&CYCLES) as usize;
+    let mut grid = mkgrid(input);
+    if target == 0 {
+        return Ok(weight(&grid));
+    }
+    let mut list: Vec<String> = Vec::new();
+    let mut found: HashMap<String,usize> = HashMap::new();
+    let key = grid_to_str(&grid);
+    list.push(key.clone());
+    found.insert(key, 0);
+    let mut meter = ctx.budget.start("repeating spin cycle", 10_000);
+    let mut cycles = 0;
+    loop {
+        cycles += 1;
+        meter.tick()?;
+        let spun = [CDir::N, CDir::W, CDir::S, CDir::E].into_iter()
+            .all(|dir| tilt(&mut grid, dir, ctx));
+        if !spun || !ctx.trace("spin_cycle", || object!{ cycle: cycles, load: weight(&grid) }) {
+            return Ok(0);
+        }
+        if cycles == target {
+            return Ok(weight(&grid));
+        }
+        let key = grid_to_str(&grid);
+        if let Some(start) = found.get(&key) {
+            let len = cycles - start;
+            let idx = (target - start) % len + start;
+            str_to_grid(&mut grid, &list[idx]);
+            return Ok(weight(&grid));
+        }
+        else {
+            list.push(key.clone());
+            found.insert(key, cycles);
+        }
+    }
+}
+
+/// The platform after tilting north, as part 1 weighs it.
+fn render(input: &[String]) -> Image {
+    let mut grid = mkgrid(input);
+    tilt(&mut grid, CDir::N, &Context::default());
+    Image::from_grid(&grid, |c| match c {
+        Cell::Round => Rgb::YELLOW,
+        Cell::Cube => Rgb::GRAY,
+        Cell::Empty => Rgb::BLACK,
+    })
+}
+
+fn generate(rng: &mut StdRng, size: usize) -> Vec<String> {
+    gen::grid(rng, size, size, |rng| match rng.gen_range(0..20) {
+        0..=3 => 'O',
+        4..=6 => '#',
+        _ => '.',
+    })
+}
+
+pub struct Day14;
+
+impl Day for Day14 {
+    const DAY: u32 = 14;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day14.testinput")];
+    const PARAMS: &'static [Param] = &[CYCLES];
+    type Input = Vec<String>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        check_grid::<Cell>(&lines, "one of `.`, `O` or `#`")?;
+        Ok(lines)
+    }
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part1(input, ctx).into()
+    }
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part2(input, ctx).into()
+    }
+    fn render(&self, input: &Self::Input, _ctx: &Context) -> Option<Image> {
+        Some(render(input))
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(100)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+    use crate::budget::Budget;
+
+    #[test]
+    fn day14_test() {
+        let input: Vec<String> = test_input(include_str!("day14.testinput"));
+        assert_eq!(part1(&input, &Context::default()), 136);
+        /*
+        let mut grid = mkgrid(&input);
+        tilt(&mut grid, CDir::N);
+        tilt(&mut grid, CDir::W);
+        tilt(&mut grid, CDir::S);
+        tilt(&mut grid, CDir::E);
+        grid.print(|c| c.to_char());
+        println!("");
+        tilt(&mut grid, CDir::N);
+        tilt(&mut grid, CDir::W);
+        tilt(&mut grid, CDir::S);
+        tilt(&mut grid, CDir::E);
+        grid.print(|c| c.to_char());
+        */
+        assert_eq!(part2(&input, &Context::default()), Ok(64));
+        let ctx = Context { budget: Budget { iterations: Some(5), ..Budget::default() }, ..Context::default() };
+        assert_eq!(part2(&input, &ctx), Err(OutOfBudget::Iterations { what: "repeating spin cycle", limit: 5 }));
+    }
+}
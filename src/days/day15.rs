@@ -0,0 +1,154 @@
+//! Day 15: Lens Library
+
+use std::str::FromStr;
+use std::vec::Vec;
+use rand::Rng;
+use crate::gen::StdRng;
+use crate::parse::{ParseError, parse_token};
+use crate::runner::{Answer, Context, Day};
+
+fn hash(s: &str) -> usize {
+    s.chars()
+        .map(|c| c as usize)
+        .fold(0, |acc, n| ((acc + n) * 17) & 255)
+}
+
+/// Sum of the HASH of each step.
+pub fn part1(input: &[String]) -> usize {
+    input.iter()
+        .map(|s| s.split(',').map(hash).sum::<usize>())
+        .sum()
+}
+
+#[derive(Clone)]
+struct Lens {
+    label: String,
+    focal: usize,
+}
+
+#[derive(Clone)]
+struct LensBox {
+    lenses: Vec<Lens>
+}
+
+impl LensBox {
+    fn new() -> Self {
+        Self { lenses: Vec::new() }
+    }
+    fn insert(&mut self, lens: Lens) {
+        if let Some(idx) = self.lenses.iter().position(|l| l.label == lens.label) {
+            self.lenses[idx] = lens;
+        }
+        else {
+            self.lenses.push(lens);
+        }
+    }
+    fn remove(&mut self, label: &str) {
+        self.lenses.retain(|l| l.label != label);
+    }
+}
+
+enum Action {
+    Add(usize),
+    Remove,
+}
+
+struct Input {
+    label: String,
+    action: Action,
+}
+impl FromStr for Input {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((label, focal)) = s.split_once('=') {
+            let focal = parse_token(s, focal, "a focal length")?;
+            Ok(Self { label: label.into(), action: Action::Add(focal) })
+        }
+        else if let Some(label) = s.strip_suffix('-') {
+            Ok(Self { label: label.into(), action: Action::Remove })
+        }
+        else {
+            Err(ParseError::at(s, s, "`LABEL=N` or `LABEL-`"))
+        }
+    }
+}
+
+/// Focusing power of the lenses after following the steps.
+pub fn part2(input: &[String]) -> usize {
+    let mut boxes = vec![LensBox::new(); 256];
+    input.iter()
+        .flat_map(|s| s.split(',').map(|ss| ss.parse::<Input>().unwrap()))
+        .for_each(|i| {
+            let h = hash(&i.label);
+            match i.action {
+                Action::Add(n) => boxes[h].insert(Lens { label: i.label, focal: n }),
+                Action::Remove => boxes[h].remove(&i.label),
+            }
+        });
+    /*
+    boxes.iter().enumerate().filter(|(_,b)| b.lenses.len() > 0).for_each(|(idx, b)| {
+        print!("box {idx}: ");
+        b.lenses.iter().for_each(|l| { print!("{} {}, ", l.label, l.focal); });
+        println!("");
+    });
+    */
+    boxes.iter().enumerate()
+        .map(|(box_idx, b)| b.lenses.iter().enumerate()
+            .map(|(l_idx, lens)| (box_idx + 1) * (l_idx + 1) * lens.focal)
+            .sum::<usize>()
+        )
+        .sum()
+}
+
+/// One line of `steps` steps, over a pool of labels small enough that
+/// labels get replaced and removed.
+fn generate(rng: &mut StdRng, steps: usize) -> Vec<String> {
+    let labels: Vec<String> = (0..(steps / 4).max(1))
+        .map(|_| (0..rng.gen_range(2..=6)).map(|_| rng.gen_range('a'..='z')).collect())
+        .collect();
+    let steps = (0..steps)
+        .map(|_| {
+            let label = &labels[rng.gen_range(0..labels.len())];
+            if rng.gen_bool(0.3) { format!("{label}-") } else { format!("{label}={}", rng.gen_range(1..=9)) }
+        })
+        .collect::<Vec<_>>();
+    vec![steps.join(",")]
+}
+
+pub struct Day15;
+
+impl Day for Day15 {
+    const DAY: u32 = 15;
+    type Input = Vec<String>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        // part 1 hashes the raw steps, so just check that they're well formed
+        for (idx, s) in lines.iter().enumerate() {
+            for ss in s.split(',') {
+                ss.parse::<Input>().map_err(|e| e.within(s, ss).with_line(idx + 1))?;
+            }
+        }
+        Ok(lines)
+    }
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part1(input).into()
+    }
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part2(input).into()
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(4000)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day15_test() {
+        let input: Vec<String> = test_input("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7");
+        assert_eq!(part1(&input), 1320);
+        assert_eq!(part2(&input), 145);
+    }
+}
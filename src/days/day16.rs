@@ -0,0 +1,173 @@
+//! Day 16: The Floor Will Be Lava
+
+use std::collections::{HashSet, VecDeque};
+use std::iter;
+use std::vec::Vec;
+use itertools::Itertools;
+use json::object;
+use rand::Rng;
+use ya_advent_lib::coords::{CDir, Coord2D};
+use ya_advent_lib::grid::Grid;
+use crate::gen::{self, StdRng};
+use crate::parse::ParseError;
+use crate::render::{Image, Rgb};
+use crate::runner::{Answer, Context, Day};
+
+/*
+enum Cell {
+    Empty,
+    HSplit,
+    VSplit,
+    FwdSlash,
+    BckSlash
+}
+*/
+
+fn energized(grid: &Grid<char>, initial_loc: Coord2D, initial_dir: CDir, ctx: &Context) -> HashSet<Coord2D> {
+    let mut energized: HashSet<Coord2D> = HashSet::new();
+    if !ctx.trace("beam_entered", || object!{ at: [initial_loc.x, initial_loc.y].as_slice(), dir: format!("{initial_dir:?}") }) {
+        return energized;
+    }
+    let mut queue: VecDeque<(CDir, Coord2D)> = VecDeque::new();
+    queue.push_back((initial_dir, initial_loc));
+    let mut traversed: HashSet<(CDir, Coord2D)> = HashSet:: new();
+    while let Some((dir, loc)) = queue.pop_front() {
+        if !grid.x_bounds().contains(&loc.x) || !grid.y_bounds().contains(&loc.y) {
+            continue;
+        }
+        if traversed.contains(&(dir, loc)) {
+            continue;
+        }
+        traversed.insert((dir, loc));
+        energized.insert(loc);
+        let split = |a: CDir, b: CDir| ctx.trace("beam_split", || object!{
+            at: [loc.x, loc.y].as_slice(),
+            from: format!("{dir:?}"),
+            to: [format!("{a:?}"), format!("{b:?}")].as_slice(),
+        });
+        match (grid.get(loc.x, loc.y), dir) {
+            ('|', CDir::W) |
+            ('|', CDir::E) => {
+                if !split(CDir::N, CDir::S) { break; }
+                queue.push_back((CDir::N, loc + CDir::N));
+                queue.push_back((CDir::S, loc + CDir::S));
+            },
+            ('-', CDir::N) |
+            ('-', CDir::S) => {
+                if !split(CDir::E, CDir::W) { break; }
+                queue.push_back((CDir::E, loc + CDir::E));
+                queue.push_back((CDir::W, loc + CDir::W));
+            },
+            ('/', CDir::N) => { queue.push_back((CDir::E, loc + CDir::E)); },
+            ('/', CDir::S) => { queue.push_back((CDir::W, loc + CDir::W)); },
+            ('/', CDir::E) => { queue.push_back((CDir::N, loc + CDir::N)); },
+            ('/', CDir::W) => { queue.push_back((CDir::S, loc + CDir::S)); },
+            ('\\', CDir::N) => { queue.push_back((CDir::W, loc + CDir::W)); },
+            ('\\', CDir::S) => { queue.push_back((CDir::E, loc + CDir::E)); },
+            ('\\', CDir::E) => { queue.push_back((CDir::S, loc + CDir::S)); },
+            ('\\', CDir::W) => { queue.push_back((CDir::N, loc + CDir::N)); },
+
+            (_, _) => { queue.push_back((dir, loc + dir)); },
+        }
+    }
+    energized
+}
+
+fn calc_energized(grid: &Grid<char>, initial_loc: Coord2D, initial_dir: CDir, ctx: &Context) -> usize {
+    energized(grid, initial_loc, initial_dir, ctx).len()
+}
+
+/// Tiles energized by a beam entering the top left going right.
+pub fn part1(input: &[String], ctx: &Context) -> usize {
+    let grid: Grid<char> = Grid::from_input(input, '.', 0);
+    calc_energized(&grid, Coord2D::new(0,0), CDir::E, ctx)
+}
+
+/// Most tiles energized by a beam entering from any edge tile.
+pub fn part2(input: &[String], ctx: &Context) -> usize {
+    let grid: Grid<char> = Grid::from_input(input, '.', 0);
+    let entries: Vec<(Coord2D, CDir)> = [CDir::E, CDir::W, CDir::S, CDir::N]
+        .into_iter()
+        .flat_map(|dir| {
+            let coords: Vec<(i64,i64)> = match dir {
+                CDir::E => Vec::from_iter(
+                    iter::once(0).cartesian_product(grid.y_bounds())
+                ),
+                CDir::W => Vec::from_iter(
+                    iter::once(grid.x_bounds().end - 1).cartesian_product(grid.y_bounds())
+                ),
+                CDir::S => Vec::from_iter(
+                    grid.x_bounds().cartesian_product(iter::once(0))
+                ),
+                CDir::N => Vec::from_iter(
+                    grid.x_bounds().cartesian_product(iter::once(grid.y_bounds().end-1))
+                ),
+            };
+            Vec::from_iter(
+                coords.iter()
+                .map(|(x, y)| (Coord2D::new(*x,*y), dir))
+            )
+        })
+        .collect();
+    ctx.map(&entries, |(loc, dir)| calc_energized(&grid, *loc, *dir, ctx))
+        .into_iter()
+        .max()
+        .unwrap()
+}
+
+/// The cells energized by the part 1 beam in yellow, or orange where the
+/// cell has a mirror or splitter.
+fn render(input: &[String]) -> Image {
+    let grid: Grid<char> = Grid::from_input(input, '.', 0);
+    let mut image = Image::from_grid(&grid, |c| if c == '.' { Rgb::BLACK } else { Rgb::WHITE });
+    let lit = energized(&grid, Coord2D::new(0,0), CDir::E, &Context::default());
+    image.overlay(lit.iter().copied().filter(|c| grid.get_c(*c) == '.'), Rgb::YELLOW);
+    image.overlay(lit.iter().copied().filter(|c| grid.get_c(*c) != '.'), Rgb::ORANGE);
+    image
+}
+
+fn generate(rng: &mut StdRng, size: usize) -> Vec<String> {
+    gen::grid(rng, size, size, |rng| match rng.gen_range(0..40) {
+        0 => '|',
+        1 => '-',
+        2 => '/',
+        3 => '\\',
+        _ => '.',
+    })
+}
+
+pub struct Day16;
+
+impl Day for Day16 {
+    const DAY: u32 = 16;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day16.testinput")];
+    type Input = Vec<String>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        Ok(lines)
+    }
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part1(input, ctx).into()
+    }
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part2(input, ctx).into()
+    }
+    fn render(&self, input: &Self::Input, _ctx: &Context) -> Option<Image> {
+        Some(render(input))
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(110)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day16_test() {
+        let input: Vec<String> = test_input(include_str!("day16.testinput"));
+        assert_eq!(part1(&input, &Context::default()), 46);
+        assert_eq!(part2(&input, &Context::default()), 51);
+    }
+}
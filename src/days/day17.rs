@@ -0,0 +1,197 @@
+//! Day 17: Clumsy Crucible
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::vec::Vec;
+use rand::Rng;
+use ya_advent_lib::grid::Grid;
+use ya_advent_lib::coords::{CDir, Coord2D};
+use ya_advent_lib::algorithm::a_star;
+use crate::gen::{self, StdRng};
+use crate::parse::{self, ParseError, check_grid};
+use crate::render::{Image, Rgb};
+use crate::runner::{Answer, Context, Day, Param};
+
+#[derive(Copy, Clone)]
+struct Cell {
+    loss: u8,
+}
+
+impl TryFrom<char> for Cell {
+    type Error = char;
+    fn try_from(v: char) -> Result<Self, char> {
+        v.to_digit(10).map(|d| Cell { loss: d as u8 }).ok_or(v)
+    }
+}
+
+type State = (Coord2D, CDir);
+
+fn mkgrid(input: &[String]) -> Grid<Cell> {
+    parse::grid(input, Cell {loss: 0}, 0)
+}
+
+fn target(grid: &Grid<Cell>) -> Coord2D {
+    Coord2D::new(grid.x_bounds().end - 1, grid.y_bounds().end - 1)
+}
+
+const START: State = (Coord2D { x: -1, y: 0 }, CDir::E);
+
+const CRUCIBLE_MIN: Param = Param { name: "crucible_min", default: 1, min: 1, step: 1, help: "fewest blocks a crucible moves before turning" };
+const CRUCIBLE_MAX: Param = Param { name: "crucible_max", default: 3, min: 1, step: 1, help: "most blocks a crucible moves before turning" };
+const ULTRA_MIN: Param = Param { name: "ultra_min", default: 4, min: 1, step: 1, help: "fewest blocks an ultra crucible moves before turning" };
+const ULTRA_MAX: Param = Param { name: "ultra_max", default: 10, min: 1, step: 1, help: "most blocks an ultra crucible moves before turning" };
+
+/// The fewest and most blocks a crucible moves in a straight line.
+type Limits = (usize, usize);
+
+fn crucible(ctx: &Context) -> Limits {
+    (ctx.param(&CRUCIBLE_MIN) as usize, ctx.param(&CRUCIBLE_MAX) as usize)
+}
+
+fn ultra_crucible(ctx: &Context) -> Limits {
+    (ctx.param(&ULTRA_MIN) as usize, ctx.param(&ULTRA_MAX) as usize)
+}
+
+fn moves(grid: &Grid<Cell>, (loc, dir): State, (minsteps, maxsteps): Limits) -> Vec<(State, usize)> {
+    if loc == START.0 {
+        vec![
+            ((Coord2D::new(0,0), CDir::E), 0),
+            ((Coord2D::new(0,0), CDir::S), 0),
+        ]
+    }
+    else {
+        let mut loc = loc;
+        let mut cost = 0;
+        let mut neighs: Vec<(State, usize)> = Vec::new();
+        for s in 1 ..= maxsteps {
+            loc += dir;
+            if !grid.contains_coord(loc) { break; }
+            cost += grid.get_c(loc).loss as usize;
+            if s < minsteps { continue; }
+            neighs.push(((loc, dir.left()), cost));
+            neighs.push(((loc, dir.right()), cost));
+        }
+        neighs
+    }
+}
+
+/// Least heat loss reaching the bottom right, if the crucible can get
+/// there at all.
+fn solve(input: &[String], limits: Limits) -> Option<usize> {
+    let grid = mkgrid(input);
+    let target = target(&grid);
+
+    a_star(
+        START,
+        |c| c.0 == target,
+        |state| moves(&grid, state, limits),
+        |c| c.0.mdist_to(&target) as usize,
+    )
+}
+
+/// The cells along a least-loss route. `a_star` only gives the cost, so
+/// this searches again keeping track of how each state was reached.
+fn best_path(grid: &Grid<Cell>, limits: Limits) -> Vec<Coord2D> {
+    let target = target(grid);
+    let mut cost: HashMap<State, usize> = HashMap::from([(START, 0)]);
+    let mut prev: HashMap<State, State> = HashMap::new();
+    let mut queue = BinaryHeap::from([Reverse((0, START))]);
+    while let Some(Reverse((c, state))) = queue.pop() {
+        if state.0 == target {
+            let mut path = vec![state.0];
+            let mut state = state;
+            while let Some(p) = prev.get(&state) {
+                let mut loc = state.0;
+                while loc != p.0 {
+                    loc += Coord2D::new((p.0.x - loc.x).signum(), (p.0.y - loc.y).signum());
+                    path.push(loc);
+                }
+                state = *p;
+            }
+            path.retain(|c| grid.contains_coord(*c));
+            return path;
+        }
+        if c > cost[&state] { continue; }
+        for (next, dc) in moves(grid, state, limits) {
+            if cost.get(&next).is_none_or(|nc| c + dc < *nc) {
+                cost.insert(next, c + dc);
+                prev.insert(next, state);
+                queue.push(Reverse((c + dc, next)));
+            }
+        }
+    }
+    Vec::new()
+}
+
+/// The heat loss map in shades of red, with the part 2 route in yellow.
+fn render(input: &[String], ctx: &Context) -> Image {
+    let grid = mkgrid(input);
+    let mut image = Image::from_grid(&grid, |c| Rgb(c.loss * 28, 0, 0));
+    image.overlay(best_path(&grid, ultra_crucible(ctx)), Rgb::YELLOW);
+    image
+}
+
+/// Least heat loss a crucible can incur reaching the bottom right.
+pub fn part1(input: &[String], ctx: &Context) -> Option<usize> {
+    solve(input, crucible(ctx))
+}
+
+/// Least heat loss an ultra crucible can incur.
+pub fn part2(input: &[String], ctx: &Context) -> Option<usize> {
+    solve(input, ultra_crucible(ctx))
+}
+
+fn generate(rng: &mut StdRng, size: usize) -> Vec<String> {
+    gen::grid(rng, size, size, |rng| rng.gen_range('1'..='9'))
+}
+
+pub struct Day17;
+
+impl Day for Day17 {
+    const DAY: u32 = 17;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day17.testinput")];
+    const PARAMS: &'static [Param] = &[CRUCIBLE_MIN, CRUCIBLE_MAX, ULTRA_MIN, ULTRA_MAX];
+    type Input = Vec<String>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        check_grid::<Cell>(&lines, "a digit")?;
+        Ok(lines)
+    }
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part1(input, ctx).into()
+    }
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part2(input, ctx).into()
+    }
+    fn render(&self, input: &Self::Input, ctx: &Context) -> Option<Image> {
+        Some(render(input, ctx))
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(141)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day17_test() {
+        let input: Vec<String> = test_input(include_str!("day17.testinput"));
+        let ctx = Context::default();
+        assert_eq!(part1(&input, &ctx), Some(102));
+        assert_eq!(part2(&input, &ctx), Some(94));
+        let grid = mkgrid(&input);
+        let path = best_path(&grid, ultra_crucible(&ctx));
+        assert_eq!(path.iter().filter(|c| **c != Coord2D::new(0, 0)).map(|c| grid.get_c(*c).loss as usize).sum::<usize>(), 94);
+
+        let input: Vec<String> = test_input(
+"111111111111
+999999999991
+999999999991
+999999999991
+999999999991
+");
+        assert_eq!(part2(&input, &ctx), Some(71));
+    }
+}
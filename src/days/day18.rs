@@ -0,0 +1,169 @@
+//! Day 18: Lavaduct Lagoon
+
+use std::str::FromStr;
+use std::vec::Vec;
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+use ya_advent_lib::coords::{CDir, Coord2D};
+use crate::gen::{self, StdRng};
+use crate::parse::{ParseError, parse_lines, parse_token};
+use crate::runner::{Answer, Context, Day};
+
+/// One step of the dig plan.
+pub struct Input {
+    dir: CDir,
+    steps: i64,
+    color: u32,
+}
+
+impl FromStr for Input {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(
+                r"(\w) (\d+) .#(\w+)"
+            ).unwrap();
+        }
+        if let Some(caps) = RE.captures(s) {
+            let d = caps.get(1).unwrap().as_str();
+            let dir = match d {
+                "U" => CDir::N,
+                "D" => CDir::S,
+                "L" => CDir::W,
+                "R" => CDir::E,
+                _ => { return Err(ParseError::at(s, d, "`U`, `D`, `L` or `R`")); },
+            };
+            let steps:i64 = parse_token(s, caps.get(2).unwrap().as_str(), "a step count")?;
+            let c = caps.get(3).unwrap().as_str();
+            let color:u32 = u32::from_str_radix(c, 16)
+                .map_err(|_| ParseError::at(s, c, "a hex color"))?;
+            if color & 0xf > 3 {
+                return Err(ParseError::at(s, &c[c.len() - 1..], "a color ending in a direction from 0 to 3"));
+            }
+            Ok(Input {dir, steps, color})
+        }
+        else {
+            Err(ParseError::at(s, s, "`DIR STEPS (#COLOR)`"))
+        }
+    }
+}
+
+impl Input {
+    /// The step hidden in the color, as part 2 reads it.
+    fn color_step(&self) -> (CDir, i64) {
+        let dir = match self.color & 0xf {
+            0 => CDir::E,
+            1 => CDir::S,
+            2 => CDir::W,
+            _ => CDir::N,
+        };
+        (dir, (self.color >> 4) as i64)
+    }
+}
+
+/// The corners of the trench dug by `steps`, starting from the origin.
+fn polygon(steps: impl Iterator<Item=(CDir, i64)>) -> Vec<Coord2D> {
+    let mut poly = Vec::new();
+    let mut pos = Coord2D::new(0, 0);
+    for (dir, n) in steps {
+        pos += Into::<Coord2D>::into(dir) * n;
+        poly.push(pos);
+    }
+    poly
+}
+
+fn polygon_from_input(input: &[Input]) -> Vec<Coord2D> {
+    polygon(input.iter().map(|i| (i.dir, i.steps)))
+}
+
+fn polygon_from_input_2(input: &[Input]) -> Vec<Coord2D> {
+    polygon(input.iter().map(Input::color_step))
+}
+
+fn poly_area(poly: &[Coord2D]) -> i64 {
+    let (sum1, sum2) = poly.iter()
+        .circular_tuple_windows()
+        .map(|(a, b)| (a.x * b.y, a.y * b.x))
+        .fold((0, 0), |(sum1, sum2), (a, b)| (sum1 + a, sum2 + b));
+    let intarea = (sum1 - sum2).abs() / 2;
+    let edgearea = poly.iter()
+        .circular_tuple_windows()
+        .map(|(a, b)| (a.x - b.x).abs() + (a.y - b.y).abs())
+        .sum::<i64>() / 2 + 1;
+    intarea + edgearea
+}
+
+/// Cubic metres of lava the lagoon holds.
+pub fn part1(input: &[Input]) -> i64 {
+    let polygon = polygon_from_input(input);
+    poly_area(&polygon)
+}
+
+/// Like part 1, with the plan read from the colour codes.
+pub fn part2(input: &[Input]) -> i64 {
+    let polygon = polygon_from_input_2(input);
+    poly_area(&polygon)
+}
+
+/// A dig plan around a random skyline of `columns` columns, with another
+/// skyline (of much longer trenches) hidden in the colors.
+fn generate(rng: &mut StdRng, columns: usize) -> Vec<String> {
+    let plan = gen::skyline(rng, columns, 10, 10);
+    let hidden = gen::skyline(rng, columns, 500000, 1000000);
+    plan.into_iter()
+        .zip(hidden)
+        .map(|((dir, len), (hdir, hlen))| {
+            let hdir = match hdir { 'R' => 0, 'D' => 1, 'L' => 2, _ => 3 };
+            format!("{dir} {len} (#{hlen:05x}{hdir})")
+        })
+        .collect()
+}
+
+pub struct Day18;
+
+impl Day for Day18 {
+    const DAY: u32 = 18;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day18.testinput")];
+    type Input = Vec<Input>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        let (count, last) = (lines.len(), lines.last().cloned().unwrap_or_default());
+        let input: Vec<Input> = parse_lines(lines)?;
+        let origin = Coord2D::new(0, 0);
+        if polygon_from_input(&input).last() != Some(&origin) {
+            return Err(ParseError::missing(&last, "a dig plan that ends where it starts").with_line(count.max(1)));
+        }
+        if polygon_from_input_2(&input).last() != Some(&origin) {
+            return Err(ParseError::missing(&last, "colors that end where they start").with_line(count));
+        }
+        Ok(input)
+    }
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part1(input).into()
+    }
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part2(input).into()
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(300)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day18_test() {
+        let input: Vec<Input> = test_input(include_str!("day18.testinput"));
+        assert_eq!(part1(&input), 62);
+        assert_eq!(part2(&input), 952408144115);
+
+        let err = |s: &str| Day18.parse(s.lines().map(String::from).collect()).err().unwrap().to_string();
+        assert_eq!(err("R 6 (#70c714)"), "line 1, column 12: expected a color ending in a direction from 0 to 3, found `4`");
+        assert_eq!(err("R 2 (#000020)\nL 1 (#000022)"), "line 2, column 14: expected a dig plan that ends where it starts, found end of line");
+        assert_eq!(err("R 2 (#000020)\nL 2 (#000012)"), "line 2, column 14: expected colors that end where they start, found end of line");
+        assert_eq!(err(""), "line 1, column 1: expected a dig plan that ends where it starts, found end of line");
+    }
+}
@@ -0,0 +1,583 @@
+//! Day 19: Aplenty
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::str::FromStr;
+use std::vec::Vec;
+use itertools::iproduct;
+use rand::Rng;
+use crate::bignum::Int;
+use crate::budget::OutOfBudget;
+use crate::gen::{self, StdRng};
+use crate::graph::Graph;
+use crate::parse::{ParseError, next_token, parse_sectioned, parse_token};
+use crate::runner::{Answer, Context, Day, Param};
+
+#[derive(Clone)]
+enum Dest {
+    Accept,
+    Reject,
+    Rule(String),
+}
+
+struct Rule {
+    rating: char,
+    op: char,
+    val: usize,
+    dest: Dest,
+}
+
+enum RuleOrDest {
+    R(Rule),
+    D(Dest),
+}
+
+/// A named workflow: rules tried in order.
+pub struct Workflow {
+    name: String,
+    rules: Vec<RuleOrDest>,
+}
+
+/// A part's ratings.
+pub struct Part {
+    x: usize,
+    m: usize,
+    a: usize,
+    s: usize,
+}
+
+impl FromStr for Dest {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "A" {
+            Ok(Dest::Accept)
+        }
+        else if s == "R" {
+            Ok(Dest::Reject)
+        }
+        else if s.is_empty() {
+            Err(ParseError::missing(s, "a destination"))
+        }
+        else if !s.chars().all(|c| c.is_ascii_lowercase()) {
+            Err(ParseError::at(s, s, "a workflow name, `A` or `R`"))
+        }
+        else {
+            Ok(Dest::Rule(s.into()))
+        }
+    }
+}
+
+impl FromStr for RuleOrDest {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut itr1 = s.split(':');
+        let first = itr1.next().unwrap();
+        if let Some(dest) = itr1.next() {
+            let token = |r: Range<usize>| first.get(r).unwrap_or(&first[first.len()..]);
+            let rating = token(0..1);
+            if !matches!(rating, "x" | "m" | "a" | "s") {
+                return Err(ParseError::at(s, rating, "a rating (x, m, a or s)"));
+            }
+            let op = token(1..2);
+            if !matches!(op, "<" | ">") {
+                return Err(ParseError::at(s, op, "`<` or `>`"));
+            }
+            let val = parse_token(s, token(2..first.len()), "a number")?;
+            let dest = dest.parse::<Dest>().map_err(|e| e.within(s, dest))?;
+            Ok(RuleOrDest::R(Rule{
+                rating: rating.chars().next().unwrap(),
+                op: op.chars().next().unwrap(),
+                val,
+                dest,
+            }))
+        }
+        else {
+            Ok(RuleOrDest::D(first.parse::<Dest>()?))
+        }
+    }
+}
+
+impl FromStr for Workflow {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, rules) = s.split_once('{')
+            .ok_or_else(|| ParseError::missing(s, "`{`"))?;
+        let rules: Vec<&str> = rules.strip_suffix('}')
+            .ok_or_else(|| ParseError::missing(s, "`}`"))?
+            .split(',')
+            .collect();
+        // rules with a condition, then where everything else goes
+        let rules = rules.iter()
+            .enumerate()
+            .map(|(idx, y)| match y.parse::<RuleOrDest>().map_err(|e| e.within(s, y))? {
+                RuleOrDest::D(_) if idx + 1 < rules.len() => Err(ParseError::at(s, y, "a rule like `x<10:dest`")),
+                RuleOrDest::R(_) if idx + 1 == rules.len() => Err(ParseError::at(s, y, "a destination for the rest")),
+                rd => Ok(rd),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Workflow{name: name.into(), rules})
+    }
+}
+
+impl FromStr for Part {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s.strip_prefix('{')
+            .and_then(|x| x.strip_suffix('}'))
+            .ok_or_else(|| ParseError::at(s, s, "`{x=N,m=N,a=N,s=N}`"))?;
+        let mut itr = inner.split(',');
+        let mut rating = |name: &str| -> Result<usize, ParseError> {
+            let expected = format!("`{name}`");
+            let tok = next_token(s, &mut itr, &expected)?;
+            let val = tok.strip_prefix(name)
+                .ok_or_else(|| ParseError::at(s, tok, &expected))?;
+            parse_token(s, val, "a rating")
+        };
+        let (x, m, a, s) = (rating("x=")?, rating("m=")?, rating("a=")?, rating("s=")?);
+        Ok(Part{x, m, a, s})
+    }
+}
+impl Part {
+    fn value(&self) -> usize {
+        self.x + self.m + self.a + self.s
+    }
+}
+
+impl Rule {
+    fn matches(&self, part: &Part) -> bool {
+        let v = match self.rating {
+            'x' => part.x,
+            'm' => part.m,
+            'a' => part.a,
+            's' => part.s,
+            _ => panic!(),
+        };
+        match self.op {
+            '<' => v < self.val,
+            '>' => v > self.val,
+            _ => panic!(),
+        }
+    }
+}
+
+impl Part {
+    fn apply_rules(&self, workflows: &HashMap<String, &Workflow>) -> Dest {
+        let mut current = String::from("in");
+        loop {
+            let wf = workflows[&current];
+            let r_or_d = wf.rules.iter().find(|rd| match rd {
+                RuleOrDest::R(r) => r.matches(self),
+                RuleOrDest::D(_) => true,
+            }).unwrap();
+            let dest = match r_or_d {
+                RuleOrDest::R(r) => r.dest.clone(),
+                RuleOrDest::D(d) => d.clone(),
+            };
+            match dest {
+                Dest::Rule(n) => {current = n;},
+                _ => {return dest;},
+            }
+        }
+    }
+}
+
+impl Dest {
+    fn name(&self) -> &str {
+        match self {
+            Dest::Accept => "A",
+            Dest::Reject => "R",
+            Dest::Rule(n) => n,
+        }
+    }
+}
+
+impl Workflow {
+    /// The workflows this one sends parts to.
+    fn dests(&self) -> impl Iterator<Item=&str> {
+        self.rules.iter()
+            .map(|rd| match rd {
+                RuleOrDest::R(r) => &r.dest,
+                RuleOrDest::D(d) => d,
+            })
+            .filter_map(|d| match d {
+                Dest::Rule(n) => Some(n.as_str()),
+                _ => None,
+            })
+    }
+}
+
+/// Checks that workflow names are unique, that there is an `in`, that
+/// every workflow a rule names exists, and that no part can be sent round
+/// in a circle. `lines` are the workflows' lines, to say where.
+fn check_workflows(workflows: &[Workflow], lines: &[String]) -> Result<(), ParseError> {
+    // where on workflow `idx`'s line it sends parts to `name`
+    let error_at = |idx: usize, name: &str, expected: &str| {
+        let s = &lines[idx];
+        let col = s.match_indices(name)
+            .map(|(col, _)| col)
+            .find(|&col| col > 0 && matches!(s.as_bytes()[col - 1], b'{' | b':' | b',')
+                && matches!(s.as_bytes().get(col + name.len()), Some(b',' | b'}')))
+            .unwrap_or(0);
+        ParseError::at(s, &s[col .. col + name.len()], expected).with_line(idx + 1)
+    };
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for (idx, wf) in workflows.iter().enumerate() {
+        if index.insert(wf.name.as_str(), idx).is_some() {
+            return Err(ParseError::at(&lines[idx], &lines[idx][..wf.name.len()], "a workflow name not used already").with_line(idx + 1));
+        }
+    }
+    for (idx, wf) in workflows.iter().enumerate() {
+        if let Some(name) = wf.dests().find(|n| !index.contains_key(n)) {
+            return Err(error_at(idx, name, "the name of a workflow, `A` or `R`"));
+        }
+    }
+    if !index.contains_key("in") {
+        let last = lines.last().map_or("", String::as_str);
+        return Err(ParseError::missing(last, "a workflow named `in`").with_line(lines.len().max(1)));
+    }
+    // depth first, without recursing, from every workflow in turn; one
+    // reached again while it's still being followed is a circle
+    let mut done = vec![false; workflows.len()];
+    let mut following = vec![false; workflows.len()];
+    for root in 0..workflows.len() {
+        if done[root] {
+            continue;
+        }
+        let mut stack = vec![(root, 0)];
+        following[root] = true;
+        while let Some((idx, n)) = stack.pop() {
+            let Some(name) = workflows[idx].dests().nth(n) else {
+                following[idx] = false;
+                done[idx] = true;
+                continue;
+            };
+            stack.push((idx, n + 1));
+            let next = index[name];
+            if following[next] {
+                return Err(error_at(idx, name, "a workflow that doesn't lead back here"));
+            }
+            if !done[next] {
+                following[next] = true;
+                stack.push((next, 0));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The workflows, with each edge labelled by the rule that takes it.
+fn dot(workflows: &[Workflow]) -> String {
+    let mut graph: Graph<String, String> = Graph::directed();
+    for wf in workflows {
+        for rd in &wf.rules {
+            let (label, dest) = match rd {
+                RuleOrDest::R(r) => (format!("{}{}{}", r.rating, r.op, r.val), &r.dest),
+                RuleOrDest::D(d) => (String::new(), d),
+            };
+            graph.add_edge(wf.name.clone(), dest.name().into(), label);
+        }
+    }
+    graph.to_dot_with(
+        |n| match n.as_str() {
+            "A" => "shape=box, color=green".into(),
+            "R" => "shape=box, color=red".into(),
+            _ => String::new(),
+        },
+        |_, _, label| if label.is_empty() { String::new() } else { format!("label=\"{label}\"") },
+    )
+}
+
+/// Sum of the ratings of the accepted parts.
+pub fn part1(input: &(Vec<Workflow>, Vec<Part>)) -> usize {
+    let wfmap: HashMap<String, &Workflow> = HashMap::from_iter(
+        input.0.iter().map(|i| (i.name.clone(), i))
+    );
+    input.1.iter()
+        .map(|part| (part.apply_rules(&wfmap), part))
+        .filter_map(|(result, part)| match result {
+            Dest::Accept => Some(part.value()),
+            _ => None,
+        })
+        .sum()
+}
+
+/// A box of part ratings, a range for each category.
+#[derive(Clone)]
+pub struct Partition {
+    x: Range<usize>,
+    m: Range<usize>,
+    a: Range<usize>,
+    s: Range<usize>,
+}
+
+impl Partition {
+    /// Every possible part, with each rating from 1 to 4000.
+    pub fn new() -> Self {
+        Self::spanning(1..4001)
+    }
+    /// Every part with each rating in `ratings`.
+    pub fn spanning(ratings: Range<usize>) -> Self {
+        Self {
+            x: ratings.clone(),
+            m: ratings.clone(),
+            a: ratings.clone(),
+            s: ratings,
+        }
+    }
+    /// The range of rating `r` (one of `xmas`).
+    pub fn get(&self, r: char) -> Range<usize> {
+        match r {
+            'x' => self.x.clone(),
+            'm' => self.m.clone(),
+            'a' => self.a.clone(),
+            's' => self.s.clone(),
+            _ => panic!(),
+        }
+    }
+    /// A copy with the range of rating `r` replaced.
+    pub fn set(&self, r: char, val: Range<usize>) -> Self {
+        let mut new = self.clone();
+        match r {
+            'x' => new.x = val,
+            'm' => new.m = val,
+            'a' => new.a = val,
+            's' => new.s = val,
+            _ => panic!(),
+        }
+        new
+    }
+    /// Number of distinct parts in the box.
+    pub fn combinations(&self) -> Int {
+        [&self.x, &self.m, &self.a, &self.s].into_iter()
+            .map(|r| Int::from(r.end - r.start))
+            .product()
+    }
+}
+
+impl Default for Partition {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const RATING_MIN: Param = Param { name: "rating_min", default: 1, min: 1, step: 1, help: "least possible rating in part 2" };
+const RATING_MAX: Param = Param { name: "rating_max", default: 4000, min: 1, step: 1, help: "greatest possible rating in part 2" };
+
+/// The possible ratings, as part 2 considers them.
+fn ratings(ctx: &Context) -> Range<usize> {
+    let min = ctx.param(&RATING_MIN) as usize;
+    let max = ctx.param(&RATING_MAX) as usize;
+    min .. (max + 1).max(min)
+}
+
+fn traverse(
+    wfmap: &HashMap<String, &Workflow>,
+    cur_wf: String,
+    cur_rule: usize,
+    cur_partition: Partition,
+) -> Vec<Partition> {
+    let mut wf = wfmap[&cur_wf];
+    let mut cur_rule = cur_rule;
+    loop {
+        match &wf.rules[cur_rule] {
+            RuleOrDest::R(r) => {
+                let range = cur_partition.get(r.rating);
+                if r.op == '<' && range.end <= r.val
+                  || r.op == '>' && range.start > r.val {
+                    match &r.dest {
+                        Dest::Accept => {
+                            return vec![cur_partition];
+                        },
+                        Dest::Reject => {
+                            return vec![];
+                        },
+                        Dest::Rule(r) => {
+                            wf = wfmap[r];
+                            cur_rule = 0;
+                        },
+                    }
+                }
+                else if r.op == '<' && range.start < r.val
+                  || r.op == '>' && range.end > r.val + 1 {
+                    let val = if r.op == '<' { r.val } else { r.val + 1 };
+                    let r1 = range.start .. val;
+                    let r2 = val .. range.end;
+                    let p1 = cur_partition.set(r.rating, r1);
+                    let p2 = cur_partition.set(r.rating, r2);
+                    let mut a1 = traverse(wfmap, wf.name.clone(), cur_rule, p1);
+                    let mut a2 = traverse(wfmap, wf.name.clone(), cur_rule, p2);
+                    a1.append(&mut a2);
+                    return a1;
+                }
+                else {
+                    cur_rule += 1;
+                }
+            },
+            RuleOrDest::D(d) => {
+                match d {
+                    Dest::Accept => {
+                        return vec![cur_partition];
+                    },
+                    Dest::Reject => {
+                        return vec![];
+                    },
+                    Dest::Rule(r) => {
+                        wf = wfmap[r];
+                        cur_rule = 0;
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// Reference for `traverse`: cuts each rating's range at every threshold
+/// the workflows test it against, and runs one part from each resulting
+/// box through the workflows.
+fn part2_reference(input: &(Vec<Workflow>, Vec<Part>), ratings: Range<usize>) -> Int {
+    let wfmap: HashMap<String, &Workflow> = HashMap::from_iter(
+        input.0.iter().map(|i| (i.name.clone(), i))
+    );
+    let mut cuts: HashMap<char, Vec<usize>> = "xmas".chars().map(|c| (c, vec![ratings.start, ratings.end])).collect();
+    for rule in input.0.iter().flat_map(|wf| wf.rules.iter()) {
+        if let RuleOrDest::R(r) = rule {
+            cuts.get_mut(&r.rating).unwrap().push(if r.op == '<' { r.val } else { r.val + 1 });
+        }
+    }
+    let spans = |c: char| {
+        let mut v: Vec<usize> = cuts[&c].iter().copied().filter(|n| (ratings.start ..= ratings.end).contains(n)).collect();
+        v.sort();
+        v.dedup();
+        v.windows(2).map(|w| (w[0], w[1] - w[0])).collect::<Vec<_>>()
+    };
+    iproduct!(spans('x'), spans('m'), spans('a'), spans('s'))
+        .filter(|(x, m, a, s)| {
+            let part = Part { x: x.0, m: m.0, a: a.0, s: s.0 };
+            matches!(part.apply_rules(&wfmap), Dest::Accept)
+        })
+        .map(|(x, m, a, s)| [x.1, m.1, a.1, s.1].into_iter().map(Int::from).product::<Int>())
+        .sum()
+}
+
+/// The boxes of ratings the workflows accept.
+fn snapshot(input: &(Vec<Workflow>, Vec<Part>), ctx: &Context) -> String {
+    let wfmap: HashMap<String, &Workflow> = HashMap::from_iter(
+        input.0.iter().map(|i| (i.name.clone(), i))
+    );
+    traverse(&wfmap, "in".into(), 0, Partition::spanning(ratings(ctx)))
+        .iter()
+        .map(|p| format!("x={:?} m={:?} a={:?} s={:?}: {}\n", p.get('x'), p.get('m'), p.get('a'), p.get('s'), p.combinations()))
+        .collect()
+}
+
+/// How many combinations of ratings the workflows accept.
+pub fn part2(input: &(Vec<Workflow>, Vec<Part>), ctx: &Context) -> Int {
+    if ctx.reference {
+        return part2_reference(input, ratings(ctx));
+    }
+    let wfmap: HashMap<String, &Workflow> = HashMap::from_iter(
+        input.0.iter().map(|i| (i.name.clone(), i))
+    );
+    traverse(&wfmap, "in".into(), 0, Partition::spanning(ratings(ctx)))
+        .iter()
+        .map(|v| v.combinations())
+        .sum()
+}
+
+/// `workflows` workflows starting from `in`, each sending parts only to
+/// later ones so that every part is eventually accepted or rejected, and
+/// 200 parts.
+fn generate(rng: &mut StdRng, workflows: usize) -> Vec<String> {
+    let mut names = gen::names(rng, workflows.max(1), 3, b"abcdefghijklmnopqrstuvwxyz");
+    names[0] = "in".into();
+    let dest = |rng: &mut StdRng, from: usize| match rng.gen_range(from..names.len() + 2) {
+        n if n == names.len() => "A".to_string(),
+        n if n == names.len() + 1 => "R".to_string(),
+        n => names[n].clone(),
+    };
+    let mut lines: Vec<String> = (0..names.len())
+        .map(|i| {
+            let rules: Vec<String> = (0..rng.gen_range(1..=3))
+                .map(|_| format!(
+                    "{}{}{}:{}",
+                    ['x', 'm', 'a', 's'][rng.gen_range(0..4)],
+                    if rng.gen_bool(0.5) { '<' } else { '>' },
+                    rng.gen_range(1..4000),
+                    dest(rng, i + 1),
+                ))
+                .collect();
+            format!("{}{{{},{}}}", names[i], rules.join(","), dest(rng, i + 1))
+        })
+        .collect();
+    lines.push(String::new());
+    lines.extend((0..200).map(|_| {
+        let [x, m, a, s] = [(); 4].map(|_| rng.gen_range(1..=4000));
+        format!("{{x={x},m={m},a={a},s={s}}}")
+    }));
+    lines
+}
+
+pub struct Day19;
+
+impl Day for Day19 {
+    const DAY: u32 = 19;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day19.testinput")];
+    const PARAMS: &'static [Param] = &[RATING_MIN, RATING_MAX];
+    type Input = (Vec<Workflow>, Vec<Part>);
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        let workflow_lines: Vec<String> = lines.iter()
+            .take_while(|l| !l.trim_end().is_empty())
+            .cloned()
+            .collect();
+        let (workflows, parts) = parse_sectioned(lines)?;
+        check_workflows(&workflows, &workflow_lines)?;
+        Ok((workflows, parts))
+    }
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part1(input).into()
+    }
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part2(input, ctx).into()
+    }
+    fn dot(&self, input: &Self::Input, _ctx: &Context) -> Option<Result<String, OutOfBudget>> {
+        Some(Ok(dot(&input.0)))
+    }
+    fn snapshot(&self, input: &Self::Input, ctx: &Context) -> Option<String> {
+        Some(snapshot(input, ctx))
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(500)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::sectioned_test_input;
+    use crate::gen;
+    use crate::parse::parse_sectioned;
+
+    #[test]
+    fn day19_test() {
+        let input: (Vec<Workflow>, Vec<Part>) = sectioned_test_input(include_str!("day19.testinput"));
+        assert_eq!(part1(&input), 19114);
+        assert_eq!(part2(&input, &Context::default()), 167409079868000i64);
+
+        let err = |s: &str| Day19.parse(s.lines().map(String::from).collect()).err().unwrap().to_string();
+        assert_eq!(err("in{x<5:ab,R}"), "line 1, column 8: expected the name of a workflow, `A` or `R`, found `ab`");
+        assert_eq!(err("in{x<5,R}"), "line 1, column 4: expected a workflow name, `A` or `R`, found `x<5`");
+        assert_eq!(err("in{ab,R}\nab{A}"), "line 1, column 4: expected a rule like `x<10:dest`, found `ab`");
+        assert_eq!(err("in{x<5:A}"), "line 1, column 4: expected a destination for the rest, found `x<5:A`");
+        assert_eq!(err("ab{A}\n\n{x=1,m=2,a=3,s=4}"), "line 1, column 6: expected a workflow named `in`, found end of line");
+        assert_eq!(err("in{x<5:ab,R}\nab{in}"), "line 2, column 4: expected a workflow that doesn't lead back here, found `in`");
+        assert_eq!(err("in{A}\nin{R}"), "line 2, column 1: expected a workflow name not used already, found `in`");
+    }
+
+    #[test]
+    fn day19_reference_test() {
+        for seed in 0..20 {
+            let input = parse_sectioned(generate(&mut gen::rng(seed), 6)).unwrap();
+            assert_eq!(part2(&input, &Context::default()), part2_reference(&input, 1..4001), "seed {seed}");
+        }
+    }
+}
@@ -0,0 +1,385 @@
+//! Day 20: Pulse Propagation
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::str::FromStr;
+use std::vec::Vec;
+use itertools::Itertools;
+use json::object;
+use num::integer::lcm;
+use crate::budget::OutOfBudget;
+use crate::graph::Graph;
+use crate::parse::{ParseError, next_token, parse_lines};
+use crate::runner::{Answer, Assumption, Context, Day};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum ModuleType {
+    Broadcaster,
+    FlipFlop,
+    Conjunction,
+    Other,
+}
+
+/// A module, as configured.
+#[derive(Clone, Debug)]
+pub struct Module {
+    name: String,
+    typ: ModuleType,
+    targets: Vec<String>,
+    inputs: HashMap<String, usize>,
+    state: usize,
+}
+
+impl FromStr for Module {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut itr = s.split(" -> ");
+        let name = itr.next().unwrap();
+        let (typ, name) = match (name, name.chars().next()) {
+            ("broadcaster", _) => (ModuleType::Broadcaster, name),
+            (_, Some('%')) if name.len() > 1 => (ModuleType::FlipFlop, &name[1..]),
+            (_, Some('&')) if name.len() > 1 => (ModuleType::Conjunction, &name[1..]),
+            _ => { return Err(ParseError::at(s, name, "`%NAME`, `&NAME` or `broadcaster`")); },
+        };
+        let targets = next_token(s, &mut itr, "` -> ` and target modules")?
+            .split(", ")
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>();
+
+        Ok(Module{
+            name: name.into(),
+            typ,
+            targets,
+            inputs: HashMap::new(),
+            state: 0,
+        })
+    }
+}
+
+impl Module {
+    fn new(name: String, typ: ModuleType) -> Self {
+        Self {
+            name,
+            typ,
+            targets: Vec::new(),
+            inputs: HashMap::new(),
+            state: 0,
+        }
+    }
+    fn set_state(&mut self, bitnum: usize, on: bool) {
+        if on {
+            self.state |= 1<<bitnum;
+        }
+        else {
+            self.state &= !(1<<bitnum);
+        }
+    }
+}
+
+/// How the modules are wired together.
+fn wiring(input: &[Module]) -> Graph<String> {
+    let mut graph = Graph::directed();
+    for i in input {
+        graph.add_node(i.name.clone());
+        for o in &i.targets {
+            graph.add_edge(i.name.clone(), o.clone(), ());
+        }
+    }
+    graph
+}
+
+/// The modules by name, each knowing its inputs, ready to run.
+pub fn setup(input: &[Module]) -> HashMap<String,Module> {
+    let graph = wiring(input);
+    let mut out: HashMap<String,Module> = input.iter()
+        .map(|i| (i.name.clone(), i.clone()))
+        .collect();
+    for name in graph.nodes() {
+        let m = out.entry(name.clone())
+            .or_insert_with(|| Module::new(name.clone(), ModuleType::Other));
+        m.inputs = graph.predecessors(name)
+            .map(|(p, _)| p.clone())
+            .unique()
+            .enumerate()
+            .map(|(n, p)| (p, n))
+            .collect();
+    }
+    out
+}
+
+/// The module network, with a node shape for each kind of module.
+fn dot(input: &[Module]) -> String {
+    let modules = setup(input);
+    wiring(input).to_dot_with(
+        |n| match modules[n].typ {
+            ModuleType::Broadcaster => "shape=doublecircle",
+            ModuleType::FlipFlop => "shape=box",
+            ModuleType::Conjunction => "shape=invtrapezium",
+            ModuleType::Other => "shape=plaintext",
+        }.into(),
+        |_, _, _| String::new(),
+    )
+}
+
+/// The module network as it runs, counting the pulses sent.
+#[allow(clippy::type_complexity)]
+pub struct System<'a> {
+    ctx: &'a Context,
+    modules: HashMap<String,Module>,
+    steps: usize,
+    n_low: usize,
+    n_high: usize,
+    breakpoints: HashMap<String, Box<dyn FnMut(String, bool, usize) + 'a>>,
+}
+
+impl<'a> System<'a> {
+    pub fn new(modules: HashMap<String,Module>, ctx: &'a Context) -> Self {
+        Self {
+            ctx,
+            modules,
+            steps: 0,
+            n_low: 0,
+            n_high: 0,
+            breakpoints: HashMap::new(),
+        }
+    }
+    /// Pushes the button once. Returns false if the trace stopped it part
+    /// way.
+    pub fn step(&mut self) -> bool {
+        self.steps += 1;
+        let mut queue: VecDeque<(String,String,bool)> = VecDeque::new();
+        queue.push_back(("broadcaster".into(), String::new(), false));
+        let steps = self.steps;
+        let ctx = self.ctx;
+        let traced = |from: &str, to: &str, high: bool| ctx.trace("pulse", || object!{
+            press: steps,
+            from: from,
+            to: to,
+            high: high,
+        });
+        if !traced("button", "broadcaster", false) {
+            return false;
+        }
+
+        while let Some((name, sender, pulse)) = queue.pop_front() {
+            let m = self.modules.get_mut(&name).unwrap();
+            if pulse { self.n_high += 1; } else { self.n_low += 1; }
+            let send = match m.typ {
+                ModuleType::Broadcaster => Some(pulse),
+                ModuleType::FlipFlop => {
+                    if !pulse {
+                        m.set_state(0, m.state == 0);
+                        if m.state == 0 { Some(false) } else { Some(true) }
+                    }
+                    else {
+                        None
+                    }
+                },
+                ModuleType::Conjunction => {
+                    let idx = m.inputs[&sender];
+                    m.set_state(idx, pulse);
+                    let all_on = 2usize.pow(m.inputs.len() as u32) - 1;
+                    if m.state == all_on {
+                        Some(false)
+                    } else {
+                        Some(true)
+                    }
+                },
+                ModuleType::Other => None,
+            };
+            if let Some(p) = send {
+                if let Some(cb) = self.breakpoints.get_mut(&m.name) {
+                    cb(m.name.clone(), p, self.steps);
+                }
+                for t in &m.targets {
+                    if !traced(&m.name, t, p) {
+                        return false;
+                    }
+                    queue.push_back((t.clone(), m.name.clone(), p));
+                }
+            }
+        }
+        true
+    }
+    /// Numbers of low and high pulses sent so far.
+    pub fn pulses(&self) -> (usize, usize) {
+        (self.n_low, self.n_high)
+    }
+    pub fn run_for(&mut self, steps: usize) {
+        for _ in 0..steps {
+            if !self.step() { break; }
+        }
+    }
+}
+
+/// Product of the numbers of low and high pulses sent over 1000 button presses.
+pub fn part1(input: &[Module], ctx: &Context) -> usize {
+    let modules = setup(input);
+    let mut system = System::new(modules, ctx);
+    system.run_for(1000);
+    let (low, high) = system.pulses();
+    low * high
+}
+
+/// What part 2 relies on: `rx` is fed by one conjunction, which is fed
+/// by conjunctions that each head a subnetwork of their own.
+fn assumptions(input: &[Module]) -> Vec<Assumption> {
+    let graph = wiring(input);
+    let modules = setup(input);
+    let feeders: Vec<&String> = graph.predecessors(&"rx".to_string()).map(|(n, _)| n).unique().collect();
+    let feeder = match feeders[..] {
+        [f] if modules[f].typ == ModuleType::Conjunction => Ok(f),
+        [f] => Err(format!("{f} isn't a conjunction")),
+        [] => Err("nothing feeds rx".to_string()),
+        _ => Err(format!("{} modules feed rx", feeders.len())),
+    };
+    let heads: Vec<&String> = feeder.iter()
+        .flat_map(|f| graph.predecessors(f).map(|(n, _)| n))
+        .unique()
+        .collect();
+    let conjunctions = feeder.as_ref().map_err(|_| "there's no one feeder".to_string()).and_then(|_| {
+        match heads.iter().find(|h| modules[**h].typ != ModuleType::Conjunction) {
+            Some(h) => Err(format!("{h} isn't a conjunction")),
+            None => Ok(()),
+        }
+    });
+    // everything upstream of a head, short of the broadcaster
+    let upstream = |head: &String| {
+        let mut seen: HashSet<String> = HashSet::from([head.clone()]);
+        let mut stack = vec![head.clone()];
+        while let Some(n) = stack.pop() {
+            for (p, _) in graph.predecessors(&n) {
+                if p != "broadcaster" && seen.insert(p.clone()) {
+                    stack.push(p.clone());
+                }
+            }
+        }
+        seen
+    };
+    let networks: Vec<(&String, HashSet<String>)> = heads.iter().map(|h| (*h, upstream(h))).collect();
+    let independent = feeder.as_ref().map_err(|_| "there's no one feeder".to_string()).and_then(|_| {
+        match networks.iter().tuple_combinations().find(|((_, a), (_, b))| !a.is_disjoint(b)) {
+            Some(((a, na), (b, nb))) => Err(format!("{a} and {b} share {} modules", na.intersection(nb).count())),
+            None => Ok(()),
+        }
+    });
+    vec![
+        Assumption::check("rx is fed by a single conjunction", feeder.map(|_| ())),
+        Assumption::check("that conjunction is fed only by conjunctions", conjunctions),
+        Assumption::check("those conjunctions' subnetworks only meet at the broadcaster", independent),
+    ]
+}
+
+/// Fewest button presses until `rx` gets a low pulse. None if nothing
+/// feeds `rx`.
+pub fn part2(input: &[Module], ctx: &Context) -> Result<Option<usize>, OutOfBudget> {
+    let graph = wiring(input);
+
+    /*
+     * From looking at the graph, the rx node is fed from a Conjuction node
+     * which in turn is fed from four other Conjuction nodes, and each of those
+     * is fed from a network of nodes. Those four networks do not have
+     * any interconnections, other than a connection to the broadcaster.
+     *
+     * So each network generates a high pulse out of its output Conjunction node
+     * at a repeating interval. So we detect the high pulses and store the step
+     * number for each, and once they've all pulsed we can LCM them to get the
+     * time in the (far) future when they all go high at the same time.
+     */
+
+    let Some((rx_feeder, _)) = graph.predecessors(&"rx".to_string()).next() else {
+        return Ok(None);
+    };
+    let counters:HashMap<String,usize> = HashMap::from_iter(
+        graph.predecessors(rx_feeder).map(|(n, _)| (n.clone(), 0))
+    );
+    if counters.is_empty() {
+        return Ok(None);
+    }
+    let counters: RefCell<HashMap<String,usize>> = RefCell::new(counters);
+    let cb = &|name, hi, steps| {
+        if hi {
+            let mut c = counters.borrow_mut();
+            c.entry(name).and_modify(|v| if *v == 0 { *v = steps; });
+        }
+    };
+    let mut system = System::new(setup(input), ctx);
+    for n in counters.borrow().keys() {
+        system.breakpoints.insert(n.clone(), Box::new(cb));
+    }
+    let mut presses = ctx.budget.start("cycle of every rx feeder", 1_000_000);
+    loop {
+        presses.tick()?;
+        if !system.step() {
+            return Ok(None);
+        }
+        if counters.borrow().values().all(|v| *v > 0) {
+            return Ok(counters.borrow().values().copied().reduce(lcm));
+        }
+    }
+}
+
+pub struct Day20;
+
+impl Day for Day20 {
+    const DAY: u32 = 20;
+    type Input = Vec<Module>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        let (count, last) = (lines.len(), lines.last().cloned().unwrap_or_default());
+        let modules: Vec<Module> = parse_lines(lines)?;
+        if !modules.iter().any(|m| m.typ == ModuleType::Broadcaster) {
+            return Err(ParseError::missing(&last, "a `broadcaster` module").with_line(count.max(1)));
+        }
+        Ok(modules)
+    }
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part1(input, ctx).into()
+    }
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part2(input, ctx).into()
+    }
+    fn dot(&self, input: &Self::Input, _ctx: &Context) -> Option<Result<String, OutOfBudget>> {
+        Some(Ok(dot(input)))
+    }
+    fn assumptions(&self, input: &Self::Input, _ctx: &Context) -> Vec<Assumption> {
+        assumptions(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day20_test() {
+        let input: Vec<Module> = test_input(
+"broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a
+");
+        assert_eq!(part1(&input, &Context::default()), 32000000);
+
+        let err = |s: &str| Day20.parse(s.lines().map(String::from).collect()).err().unwrap().to_string();
+        assert_eq!(err("bcast -> a\n%a -> b"), "line 1, column 1: expected `%NAME`, `&NAME` or `broadcaster`, found `bcast`");
+        assert_eq!(err("%a -> b\n&b -> a"), "line 2, column 8: expected a `broadcaster` module, found end of line");
+
+        let input: Vec<Module> = test_input(
+"broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output
+");
+        assert_eq!(part1(&input, &Context::default()), 11687500);
+
+        let wiring = "broadcaster -> a, b\n%a -> ca\n%b -> cb\n&ca -> hub\n&cb -> hub\n&hub -> rx\n";
+        let input: Vec<Module> = test_input(wiring);
+        assert!(assumptions(&input).iter().all(|a| a.holds()));
+        let input: Vec<Module> = test_input(&wiring.replace("%a -> ca", "%a -> ca, cb"));
+        let held: Vec<bool> = assumptions(&input).iter().map(|a| a.holds()).collect();
+        assert_eq!(held, [true, true, false]);
+    }
+}
@@ -0,0 +1,236 @@
+//! Day 21: Step Counter
+
+use std::collections::{HashSet, VecDeque};
+use std::vec::Vec;
+use rand::Rng;
+use ya_advent_lib::coords::Coord2D;
+use ya_advent_lib::grid::Grid;
+use crate::bignum::Int;
+use crate::gen::{self, StdRng};
+use crate::parse::{self, ParseError, check_grid, find_one};
+use crate::render::{Image, Rgb};
+use crate::runner::{Answer, Assumption, Context, Day, Param};
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Cell {
+    Plot,
+    Rock,
+    Start
+}
+
+impl TryFrom<char> for Cell {
+    type Error = char;
+    fn try_from(value: char) -> Result<Self, char> {
+        Ok(match value {
+            '.' => Cell::Plot,
+            '#' => Cell::Rock,
+            'S' => Cell::Start,
+            _ => { return Err(value); },
+        })
+    }
+}
+impl From<Cell> for char {
+    fn from(value: Cell) -> Self {
+        match value {
+            Cell::Plot => '.',
+            Cell::Rock => '#',
+            Cell::Start => 'S',
+        }
+    }
+}
+
+pub fn mkgrid(input: &[String]) -> Grid<Cell> {
+    parse::grid(input, Cell::Plot, 0)
+}
+
+pub fn fill<C>(grid: &Grid<Cell>, start: C, max_steps: usize, expand: bool) -> HashSet<Coord2D>
+where C: Into<Coord2D> {
+    let start: Coord2D = start.into();
+    let x_size = grid.x_bounds().end - grid.x_bounds().start;
+    let y_size = grid.y_bounds().end - grid.y_bounds().start;
+    let parity = max_steps % 2;
+    let mut out = HashSet::new();
+    let mut queue:VecDeque<(Coord2D, usize)> = VecDeque::new();
+    let mut stepped: HashSet<Coord2D> = HashSet::new();
+    queue.push_back((start, 0));
+    while let Some((pos, steps)) = queue.pop_front() {
+        if steps == max_steps || (steps != 0 && steps % 2 == parity) {
+            out.insert(pos);
+            if steps == max_steps { continue; }
+        }
+        pos.neighbors4()
+            .iter()
+            .filter(|p| (expand || grid.contains_coord(**p)) && grid.get(p.x.rem_euclid(x_size), p.y.rem_euclid(y_size)) != Cell::Rock)
+            .for_each(|p| {
+                if !stepped.contains(p) {
+                    queue.push_back((*p, steps + 1));
+                    stepped.insert(*p);
+                }
+            });
+    }
+    out
+}
+
+const STEPS: Param = Param { name: "steps", default: 64, min: 0, step: 1, help: "steps the elf takes in part 1" };
+const BIG_STEPS: Param = Param { name: "big_steps", default: 26501365, min: 65, step: 131, help: "steps the elf takes on the repeating map in part 2" };
+
+/// Garden plots reachable in exactly 64 steps.
+pub fn part1(input: &[String], ctx: &Context) -> usize {
+    let grid = mkgrid(input);
+    let (x, y) = grid.find(|c,_,_| c == Cell::Start).expect("parse found the start");
+    fill(&grid, (x, y), ctx.param(&STEPS) as usize, false).len()
+}
+
+/// Value at `x` of the quadratic through (0, `v0`), (1, `v1`) and (2, `v2`),
+/// by Neville's algorithm. Each step's division is exact once the
+/// multiplication is done first, so it all stays in integers.
+fn aitken_neville(v0: usize, v1: usize, v2: usize, x: usize) -> Int {
+    let mut p = [v0, v1, v2].map(Int::from);
+    for i in 1..3 {
+        for j in 0..3 - i {
+            let step = (Int::from(x) - Int::from(j)) * (p[j + 1].clone() - p[j].clone()) / Int::from(i);
+            p[j] = p[j].clone() + step;
+        }
+    }
+    p[0].clone()
+}
+
+/// The plots reachable in exactly 64 steps in green, around the start in
+/// red.
+fn render(input: &[String], ctx: &Context) -> Image {
+    let grid = mkgrid(input);
+    let (x, y) = grid.find(|c,_,_| c == Cell::Start).expect("parse found the start");
+    let mut image = Image::from_grid(&grid, |c| match c {
+        Cell::Plot => Rgb::BLACK,
+        Cell::Rock => Rgb::GRAY,
+        Cell::Start => Rgb::RED,
+    });
+    image.overlay(fill(&grid, (x, y), ctx.param(&STEPS) as usize, false), Rgb::GREEN);
+    image
+}
+
+fn bigfill<C>(grid: &Grid<Cell>, start: C, maxsteps: usize) -> Int
+where C: Into<Coord2D> + Copy {
+    // This works because of properties of the input:
+    // size is 131x131, start point is at center (thus the 65),
+    // and there's a clear path from the center to each edge,
+    // and there are no rocks on the edge. `maxsteps` has to be 65 plus a
+    // whole number of map widths for the fit to answer the question, which
+    // the `big_steps` parameter insists on.
+    let v0 = fill(grid, start, 65, true).len();
+    let v1 = fill(grid, start, 65 + 131, true).len();
+    let v2 = fill(grid, start, 65 + 131 * 2, true).len();
+    aitken_neville(v0, v1, v2, (maxsteps - 65) / 131)
+}
+
+/// What `bigfill` relies on about the map.
+fn assumptions(input: &[String]) -> Vec<Assumption> {
+    let grid = mkgrid(input);
+    let (w, h) = (grid.x_bounds().end, grid.y_bounds().end);
+    let start = grid.find(|c,_,_| c == Cell::Start);
+    let no_rocks = |cells: Vec<(i64, i64)>| {
+        match cells.iter().filter(|(x, y)| grid.get(*x, *y) == Cell::Rock).count() {
+            0 => Ok(()),
+            n => Err(format!("{n} rocks")),
+        }
+    };
+    let size = if (w, h) != (131, 131) {
+        Err(format!("it is {w} by {h}"))
+    } else if input.iter().any(|row| row.len() != 131) {
+        Err("its rows aren't all the same length".into())
+    } else {
+        Ok(())
+    };
+    let centred = match start {
+        Some((65, 65)) => Ok(()),
+        Some((x, y)) => Err(format!("it is at ({x}, {y})")),
+        None => Err("there is no start".into()),
+    };
+    let (sx, sy) = start.unwrap_or((w / 2, h / 2));
+    vec![
+        Assumption::check("the map is 131 by 131", size),
+        Assumption::check("the start is in the middle, at (65, 65)", centred),
+        Assumption::check("the start's row and column are clear",
+            no_rocks((0..w).map(|x| (x, sy)).chain((0..h).map(|y| (sx, y))).collect())),
+        Assumption::check("the edges are clear",
+            no_rocks((0..w).flat_map(|x| [(x, 0), (x, h - 1)]).chain((0..h).flat_map(|y| [(0, y), (w - 1, y)])).collect())),
+    ]
+}
+
+/// Garden plots reachable in exactly 26501365 steps on the infinitely repeating map.
+pub fn part2(input: &[String], ctx: &Context) -> Int {
+    let grid = mkgrid(input);
+    let (x, y) = grid.find(|c,_,_| c == Cell::Start).expect("parse found the start");
+    bigfill(&grid, (x, y), ctx.param(&BIG_STEPS) as usize)
+}
+
+/// A garden with the start in the middle, and a clear row and column
+/// through the start and around the edge. Part 2 assumes the garden is
+/// 131 plots square.
+fn generate(rng: &mut StdRng, size: usize) -> Vec<String> {
+    let size = size.max(3) | 1;
+    let mid = size / 2;
+    let mut cells = (0..size * size).map(|i| (i % size, i / size));
+    gen::grid(rng, size, size, |rng| {
+        let (x, y) = cells.next().unwrap();
+        if (x, y) == (mid, mid) {
+            'S'
+        }
+        else if x == mid || y == mid || x == 0 || y == 0 || x == size - 1 || y == size - 1 {
+            '.'
+        }
+        else if rng.gen_bool(0.15) { '#' } else { '.' }
+    })
+}
+
+pub struct Day21;
+
+impl Day for Day21 {
+    const DAY: u32 = 21;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day21.testinput")];
+    const PARAMS: &'static [Param] = &[STEPS, BIG_STEPS];
+    type Input = Vec<String>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        check_grid::<Cell>(&lines, "one of `.`, `#` or `S`")?;
+        find_one(&lines, 'S', "exactly one start `S`")?;
+        Ok(lines)
+    }
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part1(input, ctx).into()
+    }
+    fn render(&self, input: &Self::Input, ctx: &Context) -> Option<Image> {
+        Some(render(input, ctx))
+    }
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part2(input, ctx).into()
+    }
+    fn assumptions(&self, input: &Self::Input, _ctx: &Context) -> Vec<Assumption> {
+        assumptions(input)
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(131)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day21_test() {
+        let input: Vec<String> = test_input(include_str!("day21.testinput"));
+        let grid = mkgrid(&input);
+        let (x, y) = grid.find(|c,_,_| c == Cell::Start).expect("parse found the start");
+        assert_eq!(fill(&grid, (x, y), 0, false).len(), 1);
+        assert_eq!(fill(&grid, (x, y), 6, false).len(), 16);
+        assert_eq!(fill(&grid, (x, y), 10, true).len(), 50);
+        assert_eq!(fill(&grid, (x, y), 50, true).len(), 1594);
+        assert_eq!(fill(&grid, (x, y), 1000, true).len(), 668697);
+        //assert_eq!(bigfill(&grid, Coord2D::new(x, y), 5000), 16733044);
+        // (x + 1)^2, past what a u64 can hold
+        assert_eq!(aitken_neville(1, 4, 9, 1_000_000_000_000), 1_000_000_000_001i128 * 1_000_000_000_001);
+        // 1 + x + x(x - 1)/2, at the points themselves and past them
+        assert_eq!([0, 1, 2, 3, 5].map(|x| aitken_neville(1, 2, 4, x)), [1, 2, 4, 7, 16].map(Int::from));
+    }
+}
@@ -0,0 +1,239 @@
+//! Day 22: Sand Slabs
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::str::FromStr;
+use std::vec::Vec;
+use itertools::Itertools;
+use json::object;
+use rand::Rng;
+use ya_advent_lib::coords::Coord3D;
+use crate::gen::StdRng;
+use crate::parse::{ParseError, parse_lines, parse_token};
+use crate::runner::{Answer, Context, Day};
+
+#[derive(Clone)]
+/// A brick, as a set of cubes relative to its lowest corner.
+pub struct Brick {
+    blocks: HashSet<Coord3D>,
+    pos: Coord3D,
+    //size: Coord3D,
+    supports: HashSet<BrickIndex>,
+    supported_by: HashSet<BrickIndex>,
+}
+
+impl FromStr for Brick {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((a_s, b_s)) = s.split_once('~') {
+            let mut blocks = HashSet::new();
+            let a = parse_token::<Coord3D>(s, a_s, "`X,Y,Z`")?;
+            let b = parse_token::<Coord3D>(s, b_s, "`X,Y,Z`")?;
+            if !(a.x <= b.x && a.y <= b.y && a.z <= b.z) {
+                return Err(ParseError::at(s, b_s, "an end no lower than the start on every axis"));
+            }
+            for x in a.x ..= b.x {
+                for y in a.y ..= b.y {
+                    for z in a.z ..= b.z {
+                        blocks.insert(Coord3D::new(x,y,z) - a);
+                    }
+                }
+            }
+            Ok(Brick{
+                blocks,
+                pos: a,
+                //size: b - a + Coord3D::new(1,1,1),
+                supports: HashSet::new(),
+                supported_by: HashSet::new(),
+            })
+        }
+        else {
+            Err(ParseError::missing(s, "`~`"))
+        }
+    }
+}
+
+impl Brick {
+    fn world_coords(&self) -> impl Iterator<Item=Coord3D> + '_ {
+        self.blocks.iter().map(|c| *c + self.pos)
+    }
+}
+
+type BrickIndex = usize;
+
+/// The bricks, and which block of space each occupies.
+pub struct Volume {
+    bricks: Vec<Brick>,
+    ordered: Vec<BrickIndex>,
+    blocks: HashMap<Coord3D, BrickIndex>,
+}
+impl Volume {
+    pub fn new(input: &[Brick]) -> Self {
+        let bricks: Vec<Brick> = input.into();
+        let mut ordered = Vec::from_iter(0..bricks.len());
+        ordered.sort_by_key(|idx| bricks[*idx].pos.z);
+        let blocks = HashMap::from_iter(
+            input.iter()
+            .enumerate()
+            .flat_map(|(idx, b)| b.world_coords().map(move |c| (c, idx)))
+        );
+        //println!("total blocks: {}", blocks.len());
+        Self { bricks, blocks, ordered }
+    }
+
+    /// Lets every brick fall as far as it can, lowest first.
+    pub fn drop(&mut self, ctx: &Context) {
+        for idx in self.ordered.clone() {
+            if !self.drop_brick(idx, ctx) {
+                break;
+            }
+        }
+        //println!("total blocks after drop: {}", self.blocks.len());
+        self.ordered.sort_by_key(|idx| self.bricks[*idx].pos.z);
+    }
+
+    /// Lets one brick fall. Returns false if the trace says to stop.
+    fn drop_brick(&mut self, idx: BrickIndex, ctx: &Context) -> bool {
+        let brick = self.bricks.get_mut(idx).unwrap();
+        let from_z = brick.pos.z;
+        let foot = brick.blocks.iter().filter(|c| c.z == 0).collect::<Vec<_>>();
+
+        brick.world_coords().for_each(|c| {self.blocks.remove(&c);});
+        while brick.pos.z > 0 {
+            let nc = brick.pos - Coord3D::z();
+            brick.supported_by = HashSet::from_iter(
+                foot.iter().map(|&&c| c + nc).filter_map(|c| self.blocks.get(&c)).copied()
+            );
+            if !brick.supported_by.is_empty() {
+                break;
+            }
+            brick.pos = nc;
+        }
+        brick.world_coords().for_each(|c| {self.blocks.insert(c, idx);});
+        let (to_z, supported_by) = (brick.pos.z, brick.supported_by.clone());
+        for sup in &supported_by {
+            let sb = self.bricks.get_mut(*sup).unwrap();
+            sb.supports.insert(idx);
+        }
+        ctx.trace("brick_settled", || object!{
+            brick: idx,
+            from_z: from_z,
+            to_z: to_z,
+            on: supported_by.into_iter().sorted().collect::<Vec<_>>(),
+        })
+    }
+
+    /// How many other bricks would fall if brick `idx` were
+    /// disintegrated. Only meaningful once the bricks have been dropped.
+    pub fn would_fall(&self, idx: BrickIndex) -> usize {
+        let mut queue: VecDeque<BrickIndex> = VecDeque::new();
+        let mut falling: HashSet<BrickIndex> = HashSet::new();
+        queue.push_back(idx);
+        falling.insert(idx);
+        while let Some(idx) = queue.pop_front() {
+            let brick = &self.bricks[idx];
+            brick.supports.iter().for_each(|sup| {
+                let sb = &self.bricks[*sup];
+                if !falling.contains(sup) && sb.supported_by.iter().all(|b| falling.contains(b)) {
+                    queue.push_back(*sup);
+                    falling.insert(*sup);
+                }
+            });
+        }
+        falling.len() - 1       // don't count the idx brick itself
+    }
+}
+
+fn setup(input: &[Brick], ctx: &Context) -> Volume {
+    let mut volume = Volume::new(input);
+    volume.drop(ctx);
+    volume
+}
+
+/// Where each brick settles, and which bricks it rests on and holds up.
+fn snapshot(volume: &Volume) -> String {
+    volume.bricks.iter()
+        .enumerate()
+        .map(|(idx, b)| format!(
+            "{idx} at {},{},{}: on {:?}, under {:?}\n",
+            b.pos.x, b.pos.y, b.pos.z,
+            b.supported_by.iter().sorted().collect::<Vec<_>>(),
+            b.supports.iter().sorted().collect::<Vec<_>>(),
+        ))
+        .collect()
+}
+
+/// Bricks that could be disintegrated without any others falling.
+pub fn part1(volume: &Volume) -> usize {
+    volume.bricks.iter()
+        .filter(|brick|
+            !brick.supports.iter().any(|sb| volume.bricks[*sb].supported_by.len() == 1)
+        )
+        .count()
+}
+
+/// Sum, over each brick, of how many others would fall if it were disintegrated.
+pub fn part2(volume: &Volume, ctx: &Context) -> usize {
+    let bricks: Vec<BrickIndex> = (0..volume.bricks.len()).collect();
+    ctx.map(&bricks, |idx| volume.would_fall(*idx))
+        .into_iter()
+        .sum()
+}
+
+/// `bricks` bricks of up to four cubes, floating over a 10 by 10 area,
+/// each a little above the last.
+fn generate(rng: &mut StdRng, bricks: usize) -> Vec<String> {
+    let mut z = 0;
+    (0..bricks)
+        .map(|_| {
+            z += rng.gen_range(1..=3);
+            let start = [rng.gen_range(0..10), rng.gen_range(0..10), z];
+            let mut end = start;
+            let axis = rng.gen_range(0..3);
+            end[axis] += rng.gen_range(0..4);
+            if axis < 2 {
+                end[axis] = end[axis].min(9);
+            }
+            z = end[2];
+            format!("{},{},{}~{},{},{}", start[0], start[1], start[2], end[0], end[1], end[2])
+        })
+        .collect()
+}
+
+pub struct Day22;
+
+impl Day for Day22 {
+    const DAY: u32 = 22;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day22.testinput")];
+    type Input = Vec<Brick>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        parse_lines(lines)
+    }
+    // Each part settles the bricks itself, so that settling can be traced
+    // and every run of a part, under --bench, pays for it.
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part1(&setup(input, ctx)).into()
+    }
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part2(&setup(input, ctx), ctx).into()
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(1200)))
+    }
+    fn snapshot(&self, input: &Self::Input, ctx: &Context) -> Option<String> {
+        Some(snapshot(&setup(input, ctx)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day22_test() {
+        let input: Vec<Brick> = test_input(include_str!("day22.testinput"));
+        let volume = setup(&input, &Context::default());
+        assert_eq!(part1(&volume), 5);
+        assert_eq!(part2(&volume, &Context::default()), 7);
+    }
+}
@@ -0,0 +1,286 @@
+//! Day 23: A Long Walk
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::vec::Vec;
+use itertools::Itertools;
+use ya_advent_lib::grid::Grid;
+use ya_advent_lib::coords::{Coord2D, CDir};
+use crate::budget::OutOfBudget;
+use crate::graph::Graph;
+use crate::parse::{self, ParseError, check_grid};
+use crate::render::{Image, Rgb};
+use crate::runner::{Answer, Assumption, Context, Day};
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Cell {
+    Wall,
+    Open,
+    Slope(CDir),
+}
+impl TryFrom<char> for Cell {
+    type Error = char;
+    fn try_from(c: char) -> Result<Self, char> {
+        Ok(match c {
+            '#' => Cell::Wall,
+            '.' => Cell::Open,
+            '>' => Cell::Slope(CDir::E),
+            '<' => Cell::Slope(CDir::W),
+            '^' => Cell::Slope(CDir::N),
+            'v' => Cell::Slope(CDir::S),
+            _ => { return Err(c); },
+        })
+    }
+}
+
+fn dir_from(a: Coord2D, b: Coord2D) -> CDir {
+    for d in [CDir::N, CDir::E, CDir::S, CDir::W] {
+        if a + d == b { return d; }
+    }
+    panic!();
+}
+
+/// Length of the longest route from `from` to `to` that doesn't visit
+/// any junction twice.
+fn traverse(graph: &Graph<Coord2D, usize>, from: Coord2D, to: Coord2D) -> usize {
+    let traversed = HashSet::from_iter([from]);
+    traverse_r(graph, from, to, traversed)
+}
+
+fn traverse_r(graph: &Graph<Coord2D, usize>, from: Coord2D, to: Coord2D, traversed: HashSet<Coord2D>) -> usize {
+    assert!(graph.contains_node(&from));
+    assert!(graph.contains_node(&to));
+
+    let mut ret = 0;
+    for (next, dx) in graph.neighbors(&from) {
+        if *next == to {
+            return *dx;
+        }
+        if !traversed.contains(next) {
+            let mut t = traversed.clone();
+            t.insert(*next);
+            ret = ret.max(dx + traverse_r(graph, *next, to, t));
+        }
+    }
+    ret
+}
+
+/// The junctions along a longest route from `from` to `to`, with its
+/// length.
+fn longest_route(graph: &Graph<Coord2D, usize>, from: Coord2D, to: Coord2D, traversed: &mut Vec<Coord2D>) -> Option<(usize, Vec<Coord2D>)> {
+    traversed.push(from);
+    let mut best: Option<(usize, Vec<Coord2D>)> = None;
+    for (next, dx) in graph.neighbors(&from) {
+        let found = if *next == to {
+            Some((*dx, traversed.iter().copied().chain([to]).collect()))
+        }
+        else if !traversed.contains(next) {
+            longest_route(graph, *next, to, traversed).map(|(len, route)| (dx + len, route))
+        }
+        else {
+            None
+        };
+        if let Some(found) = found {
+            if best.as_ref().is_none_or(|b| found.0 > b.0) {
+                best = Some(found);
+            }
+        }
+    }
+    traversed.pop();
+    best
+}
+
+fn insert_edge(graph: &mut Graph<Coord2D, usize>, a: Coord2D, b: Coord2D, dx: usize) {
+    // println!("edge {a} -> {b} {dx}");
+    match graph.weight(&a, &b) {
+        Some(w) => { assert_eq!(*w, dx); },
+        None => { graph.add_edge(a, b, dx); },
+    }
+}
+
+/// The cells of the corridor from junction `a` to junction `b`, not
+/// passing through any other junction.
+fn corridor(grid: &Grid<Cell>, graph: &Graph<Coord2D, usize>, a: Coord2D, b: Coord2D) -> Vec<Coord2D> {
+    let mut prev: HashMap<Coord2D, Coord2D> = HashMap::new();
+    let mut queue: VecDeque<Coord2D> = VecDeque::from([a]);
+    while let Some(pos) = queue.pop_front() {
+        if pos == b { break; }
+        for n in pos.neighbors4() {
+            if grid.contains_coord(n) && grid.get_c(n) != Cell::Wall && n != a && !prev.contains_key(&n)
+                    && (n == b || !graph.contains_node(&n)) {
+                prev.insert(n, pos);
+                queue.push_back(n);
+            }
+        }
+    }
+    let mut cells = vec![b];
+    while let Some(p) = prev.get(cells.last().unwrap()) {
+        cells.push(*p);
+    }
+    cells
+}
+
+fn build_graph(grid: &Grid<Cell>, start: Coord2D, end: Coord2D, part2: bool) -> Graph<Coord2D, usize> {
+    let mut queue: VecDeque<(Coord2D, Coord2D, Coord2D, usize)> = VecDeque::new();
+    let mut graph = Graph::directed();
+    queue.push_back((start, start, start, 0));
+    while let Some((anchor, from, pos, steps)) = queue.pop_front() {
+        if pos == end {
+            insert_edge(&mut graph, anchor, pos, steps);
+            continue;
+        }
+        if graph.contains_node(&pos) {
+            if graph.contains_edge(&anchor, &pos) {
+                continue;
+            }
+            insert_edge(&mut graph, anchor, pos, steps);
+        }
+        let nei = pos.neighbors4().into_iter()
+            .filter(|c| grid.contains_coord(*c) && *c != anchor && *c != from)
+            .filter(|c| matches!(grid.get_c(*c), Cell::Open | Cell::Slope(_)))
+            .collect::<Vec<_>>();
+        match nei.len() {
+            1 => {
+                queue.push_back((anchor, pos, nei[0], steps + 1));
+            },
+            n if n > 1 => {
+                insert_edge(&mut graph, anchor, pos, steps);
+                for n in nei {
+                    if !part2 && match grid.get_c(n) {
+                            Cell::Open => false,
+                            Cell::Slope(d) if dir_from(pos, n) == d => false,
+                            _ => true,
+                        } { continue; }
+                    queue.push_back((pos, pos, n, 1));
+                }
+            },
+            _ => {},
+        }
+    }
+    graph
+}
+
+fn run(input: &[String], part2: bool) -> usize {
+    let grid = parse::grid(input, Cell::Wall, 0);
+    let start = Coord2D::new(1, 0);
+    let end = Coord2D::new(grid.x_bounds().end - 2, grid.y_bounds().end - 1);
+    let graph = build_graph(&grid, start, end, part2);
+    traverse(&graph, start, end)
+}
+
+/// The longest part 1 hike in yellow, with the junctions it passes
+/// through in red.
+fn render(input: &[String]) -> Image {
+    let grid = parse::grid(input, Cell::Wall, 0);
+    let start = Coord2D::new(1, 0);
+    let end = Coord2D::new(grid.x_bounds().end - 2, grid.y_bounds().end - 1);
+    let graph = build_graph(&grid, start, end, false);
+    let mut image = Image::from_grid(&grid, |c| match c {
+        Cell::Wall => Rgb::DARK_GRAY,
+        Cell::Open => Rgb::BLACK,
+        Cell::Slope(_) => Rgb::BLUE,
+    });
+    if let Some((_, route)) = longest_route(&graph, start, end, &mut Vec::new()) {
+        for (a, b) in route.iter().zip(route.iter().skip(1)) {
+            image.overlay(corridor(&grid, &graph, *a, *b), Rgb::YELLOW);
+        }
+        image.overlay(route, Rgb::RED);
+    }
+    image
+}
+
+/// The part 1 junction graph, with the length of each path between
+/// junctions.
+fn dot(input: &[String]) -> String {
+    let grid = parse::grid(input, Cell::Wall, 0);
+    let start = Coord2D::new(1, 0);
+    let end = Coord2D::new(grid.x_bounds().end - 2, grid.y_bounds().end - 1);
+    build_graph(&grid, start, end, false).to_dot_with(
+        |n| if *n == start || *n == end { "shape=box".into() } else { String::new() },
+        |_, _, dx| format!("label={dx}"),
+    )
+}
+
+/// What the solver relies on: the hike runs from (1, 0) to one in from
+/// the bottom right corner, the only gaps in the top and bottom rows.
+fn assumptions(input: &[String]) -> Vec<Assumption> {
+    let grid = parse::grid(input, Cell::Wall, 0);
+    let (w, h) = (grid.x_bounds().end, grid.y_bounds().end);
+    let only_gap = |x, y| {
+        let gaps: Vec<_> = (0..w).filter(|x| grid.get(*x, y) != Cell::Wall).collect();
+        if gaps == [x] { Ok(()) } else { Err(format!("row {y} is open at x = {gaps:?}")) }
+    };
+    vec![
+        Assumption::check("the start is at (1, 0), the only gap in the top row", only_gap(1, 0)),
+        Assumption::check("the end is one in from the bottom right corner, the only gap in the bottom row",
+            only_gap(w - 2, h - 1)),
+    ]
+}
+
+/// The junction graphs for both parts, with the length of each path.
+fn snapshot(input: &[String]) -> String {
+    let grid = parse::grid(input, Cell::Wall, 0);
+    let start = Coord2D::new(1, 0);
+    let end = Coord2D::new(grid.x_bounds().end - 2, grid.y_bounds().end - 1);
+    let mut out = String::new();
+    for part2 in [false, true] {
+        out += &format!("part {}:\n", if part2 { 2 } else { 1 });
+        let graph = build_graph(&grid, start, end, part2);
+        for (a, b, dx) in graph.edges().sorted_by_key(|(a, b, _)| ((a.y, a.x), (b.y, b.x))) {
+            out += &format!("  ({},{}) -> ({},{}): {dx}\n", a.x, a.y, b.x, b.y);
+        }
+    }
+    out
+}
+
+/// Steps in the longest hike, going down slopes only.
+pub fn part1(input: &[String]) -> usize {
+    run(input, false)
+}
+
+/// Steps in the longest hike, treating slopes as paths.
+pub fn part2(input: &[String]) -> usize {
+    run(input, true)
+}
+
+pub struct Day23;
+
+impl Day for Day23 {
+    const DAY: u32 = 23;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day23.testinput")];
+    type Input = Vec<String>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        check_grid::<Cell>(&lines, "one of `#`, `.`, `>`, `<`, `^` or `v`")?;
+        Ok(lines)
+    }
+    fn part1(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part1(input).into()
+    }
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part2(input).into()
+    }
+    fn render(&self, input: &Self::Input, _ctx: &Context) -> Option<Image> {
+        Some(render(input))
+    }
+    fn dot(&self, input: &Self::Input, _ctx: &Context) -> Option<Result<String, OutOfBudget>> {
+        Some(Ok(dot(input)))
+    }
+    fn assumptions(&self, input: &Self::Input, _ctx: &Context) -> Vec<Assumption> {
+        assumptions(input)
+    }
+    fn snapshot(&self, input: &Self::Input, _ctx: &Context) -> Option<String> {
+        Some(snapshot(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day23_test() {
+        let input: Vec<String> = test_input(include_str!("day23.testinput"));
+        assert_eq!(part1(&input), 94);
+        assert_eq!(part2(&input), 154);
+    }
+}
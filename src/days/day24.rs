@@ -0,0 +1,158 @@
+//! Day 24: Never Tell Me The Odds
+
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::vec::Vec;
+use itertools::Itertools;
+use rand::Rng;
+use ya_advent_lib::geom::{Point2D, Point3D, Ray2D};
+use crate::gen::StdRng;
+use crate::parse::{ParseError, parse_lines, parse_token};
+use crate::runner::{Answer, Context, Day, Param};
+
+/// A hailstone's position and velocity.
+#[derive(Copy, Clone)]
+pub struct Stone {
+    pos: Point3D,
+    vel: Point3D,
+}
+
+impl FromStr for Stone {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (p, v) = s.split_once(" @ ").ok_or_else(|| ParseError::missing(s, "` @ `"))?;
+        let pos = parse_token(s, p, "a position `X, Y, Z`")?;
+        let vel = parse_token(s, v, "a velocity `X, Y, Z`")?;
+        Ok(Stone{pos, vel})
+    }
+}
+
+impl Stone {
+    fn intersect_2d(&self, other: &Stone, range: &RangeInclusive<f64>) -> bool {
+        let a = Ray2D::new(Point2D::new(self.pos.x, self.pos.y), Point2D::new(self.vel.x, self.vel.y));
+        let b = Ray2D::new(Point2D::new(other.pos.x, other.pos.y), Point2D::new(other.vel.x, other.vel.y));
+        if let Some(c) = a.intersect_with(&b) {
+            range.contains(&c.x) && range.contains(&c.y)
+        }
+        else {
+            false
+        }
+    }
+}
+
+fn num_2d_intersections(input: &[Stone], range: RangeInclusive<f64>) -> usize {
+    input.iter()
+        .tuple_combinations()
+        .filter(|(a, b)| a.intersect_2d(b, &range))
+        .count()
+}
+
+const WINDOW_MIN: Param = Param { name: "window_min", default: 200000000000000, min: 0, step: 1, help: "low X and Y of the test area in part 1" };
+const WINDOW_MAX: Param = Param { name: "window_max", default: 400000000000000, min: 0, step: 1, help: "high X and Y of the test area in part 1" };
+
+/// Pairs of hailstone paths that cross within the test area, ignoring Z.
+pub fn part1(input: &[Stone], ctx: &Context) -> usize {
+    num_2d_intersections(input, ctx.param(&WINDOW_MIN) as f64 ..= ctx.param(&WINDOW_MAX) as f64)
+}
+
+fn independent(a: Point3D, b: Point3D) -> bool {
+    let c = a.cross(b);
+    c.x != 0.0 || c.y != 0.0 || c.z != 0.0
+}
+
+fn find_plane(s1: &Stone, s2: &Stone) -> (Point3D, f64) {
+    let pdiff = s1.pos - s2.pos;
+    let vdiff = s1.vel - s2.vel;
+    let vv = s1.vel.cross(s2.vel);
+    (pdiff.cross(vdiff), pdiff.dot(vv))
+}
+
+fn lin(r: f64, a: Point3D, s: f64, b: Point3D, t: f64, c: Point3D) -> Point3D {
+    Point3D::new(
+         r * a.x + s * b.x + t * c.x,
+         r * a.y + s * b.y + t * c.y,
+         r * a.z + s * b.z + t * c.z,
+    )
+}
+
+/// Sum of the start coordinates of a rock thrown to hit every hailstone.
+pub fn part2(input: &[Stone]) -> i64 {
+    /* this comes from:
+https://www.reddit.com/r/adventofcode/comments/18pnycy/comment/kersplf/?utm_source=share&utm_medium=web3x&utm_name=web3xcss&utm_term=1&utm_content=share_button
+    */
+    let (stone_a, stone_b, stone_c) = input.iter()
+        .tuple_combinations()
+        .find(|(a, b, c)| independent(a.vel, b.vel) && independent(a.vel, c.vel) && independent(b.vel, c.vel))
+        .unwrap();
+    let (a, aa) = find_plane(stone_a, stone_b);
+    let (b, bb) = find_plane(stone_a, stone_c);
+    let (c, cc) = find_plane(stone_b, stone_c);
+
+    let w = lin(aa, b.cross(c), bb, c.cross(a), cc, a.cross(b));
+    let t = a.dot(b.cross(c));
+
+    let w = Point3D::new((w.x / t).round(), (w.y / t).round(), (w.z / t).round());
+    let w1 = stone_a.vel - w;
+    let w2 = stone_b.vel - w;
+    let ww = w1.cross(w2);
+
+    let ee = ww.dot(stone_b.pos.cross(w2));
+    let ff = ww.dot(stone_a.pos.cross(w1));
+    let gg = stone_a.pos.dot(ww);
+    let ss = ww.dot(ww);
+
+    let rock = lin(ee, w1, -ff, w2, gg, ww);
+    ((rock.x + rock.y + rock.z) / ss) as i64
+}
+
+/// `stones` hailstones, all of which a rock thrown from somewhere in the
+/// part 1 test area would hit.
+fn generate(rng: &mut StdRng, stones: usize) -> Vec<String> {
+    let coord = |rng: &mut StdRng| rng.gen_range(200_000_000_000_000i64..=400_000_000_000_000);
+    let speed = |rng: &mut StdRng| rng.gen_range(-300i64..=300);
+    let rock = ([(); 3].map(|_| coord(rng)), [(); 3].map(|_| speed(rng)));
+    (0..stones)
+        .map(|_| {
+            let t = rng.gen_range(100_000_000_000i64..1_000_000_000_000);
+            let vel = [(); 3].map(|_| speed(rng));
+            let pos: Vec<i64> = (0..3).map(|i| rock.0[i] + t * (rock.1[i] - vel[i])).collect();
+            format!("{}, {}, {} @ {}, {}, {}", pos[0], pos[1], pos[2], vel[0], vel[1], vel[2])
+        })
+        .collect()
+}
+
+pub struct Day24;
+
+impl Day for Day24 {
+    const DAY: u32 = 24;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day24.testinput")];
+    const PARAMS: &'static [Param] = &[WINDOW_MIN, WINDOW_MAX];
+    type Input = Vec<Stone>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        parse_lines(lines)
+    }
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part1(input, ctx).into()
+    }
+    fn part2(&self, input: &Self::Input, _ctx: &Context) -> Answer {
+        part2(input).into()
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(300)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day24_test() {
+        let input: Vec<Stone> = test_input(include_str!("day24.testinput"));
+        assert_eq!(num_2d_intersections(&input, 7.0..=27.0), 2);
+        let params = [("window_min".to_string(), "7".to_string()), ("window_max".to_string(), "27".to_string())].into();
+        assert_eq!(part1(&input, &Context { params, ..Context::default() }), 2);
+        assert_eq!(part2(&input), 47);
+    }
+}
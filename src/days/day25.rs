@@ -0,0 +1,186 @@
+//! Day 25: Snowverload
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::vec::Vec;
+use itertools::Itertools;
+use rand::seq::IteratorRandom;
+use crate::budget::OutOfBudget;
+use crate::gen::{self, StdRng};
+use crate::graph::Graph;
+use crate::parse::{ParseError, parse_lines};
+use crate::runner::{Answer, Context, Day};
+
+/// One line of the wiring diagram: a component and the ones it connects to.
+#[derive(Clone)]
+pub struct Input {
+    comp: String,
+    connections: HashSet<String>,
+}
+
+impl FromStr for Input {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (comp, l) = s.split_once(": ").ok_or_else(|| ParseError::missing(s, "`: `"))?;
+        let connections = HashSet::from_iter(l.split(' ').map(|ss| ss.into()));
+        Ok(Input{comp: comp.into(), connections})
+    }
+}
+
+fn mkgraph(input: &[Input]) -> Graph<String> {
+    let mut graph = Graph::undirected();
+    for i in input.iter() {
+        // In order, so the same input always makes the same graph.
+        for j in i.connections.iter().sorted() {
+            // A wire may be listed from both of its ends.
+            if !graph.contains_edge(&i.comp, j) {
+                graph.add_edge(i.comp.clone(), j.clone(), ());
+            }
+        }
+    }
+    graph
+}
+
+/// The product of the sizes of the two groups, and the three wires
+/// between them. Each round samples some more paths and tries cutting the
+/// three wires they used most. The sampling is seeded, so every call
+/// finds the same cut.
+fn find_cut(input: &[Input], ctx: &Context) -> Result<(usize, Vec<(String, String)>), OutOfBudget> {
+    let mut graph = mkgraph(input);
+    let mut seen_edges: HashMap<(String,String),usize> = HashMap::new();
+    let mut rounds = ctx.budget.start("three-wire cut", 1000);
+    let mut rng = gen::rng(0);
+    loop {
+        rounds.tick()?;
+        for _ in 0..20 {
+            let nodes = graph.nodes().choose_multiple(&mut rng, 2);
+            if let Some(path) = graph.path(nodes[0], nodes[1]) {
+                path
+                .iter()
+                .tuple_windows()
+                .for_each(|(a, b)| {
+                    let key = ((*a).min(*b).clone(), (*a).max(*b).clone());
+                    seen_edges.entry(key).and_modify(|n| *n += 1).or_insert(1);
+                });
+            }
+        }
+        let rem = seen_edges.iter()
+            .sorted_by_key(|(k, n)| (Reverse(**n), *k))
+            .map(|(k, _)| k)
+            .take(3)
+            .collect::<Vec<_>>();
+
+        rem.iter().for_each(|(a, b)| { graph.remove_edge(a, b); });
+        let n1 = graph.bfs(&rem[0].0).len();
+        let n2 = graph.bfs(&rem[0].1).len();
+        if n1 + n2 == graph.len() {
+            return Ok((n1 * n2, rem.into_iter().cloned().collect()));
+        }
+        rem.iter().for_each(|(a, b)| graph.add_edge(a.clone(), b.clone(), ()));
+    }
+}
+
+/// Product of the sizes of the two groups left after cutting three wires.
+pub fn part1(input: &[Input], ctx: &Context) -> Result<usize, OutOfBudget> {
+    find_cut(input, ctx).map(|(n, _)| n)
+}
+
+/// The wiring, with the three wires to cut in red.
+fn dot(input: &[Input], ctx: &Context) -> Result<String, OutOfBudget> {
+    let (_, cut) = find_cut(input, ctx)?;
+    Ok(mkgraph(input).to_dot_with(
+        |_| String::new(),
+        |a, b, _| if cut.iter().any(|(x, y)| (x, y) == (a, b) || (x, y) == (b, a)) {
+            "color=red, penwidth=3".into()
+        } else {
+            String::new()
+        },
+    ))
+}
+
+/// Two well connected groups of components, about `size` in all, joined
+/// by three wires.
+fn generate(rng: &mut StdRng, size: usize) -> Vec<String> {
+    let half = (size / 2).max(8);
+    let names = gen::names(rng, half * 2, 3, b"abcdefghijklmnopqrstuvwxyz");
+    let mut wires: HashSet<(usize, usize)> = HashSet::new();
+    for group in [0, half] {
+        for i in 0..half {
+            wires.insert((group + i, group + (i + 1) % half));
+            for j in (0..half).filter(|j| *j != i).choose_multiple(rng, 4) {
+                wires.insert((group + i, group + j));
+            }
+        }
+    }
+    for (a, b) in (0..half).choose_multiple(rng, 3).into_iter().zip((half..half * 2).choose_multiple(rng, 3)) {
+        wires.insert((a, b));
+    }
+    let mut conns: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (a, b) in wires {
+        if !conns.get(&b).is_some_and(|c| c.contains(&a)) {
+            conns.entry(a).or_default().push(b);
+        }
+    }
+    conns.into_iter()
+        .sorted()
+        .map(|(a, bs)| format!("{}: {}", names[a], bs.iter().map(|b| &names[*b]).join(" ")))
+        .collect()
+}
+
+pub struct Day25;
+
+impl Day for Day25 {
+    const DAY: u32 = 25;
+    const EXAMPLES: &'static [&'static str] = &[include_str!("day25.testinput")];
+    type Input = Vec<Input>;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError> {
+        parse_lines(lines)
+    }
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Answer {
+        part1(input, ctx).into()
+    }
+    fn part2(&self, _input: &Self::Input, _ctx: &Context) -> Answer {
+        Answer::None
+    }
+    fn dot(&self, input: &Self::Input, ctx: &Context) -> Option<Result<String, OutOfBudget>> {
+        Some(dot(input, ctx))
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Some(generate(rng, size.unwrap_or(1500)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budget::Budget;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day25_test() {
+        let input: Vec<Input> = test_input(include_str!("day25.testinput"));
+        assert_eq!(part1(&input, &Context::default()), Ok(54));
+    }
+
+    #[test]
+    fn day25_dot_test() {
+        let input: Vec<Input> = test_input(include_str!("day25.testinput"));
+        let graph = dot(&input, &Context::default()).unwrap();
+        assert_eq!(graph.matches("color=red").count(), 3);
+        let ctx = Context { budget: Budget { iterations: Some(0), ..Budget::default() }, ..Context::default() };
+        assert!(dot(&input, &ctx).is_err());
+    }
+
+    #[test]
+    fn day25_wires_listed_twice_test() {
+        let mut input: Vec<Input> = test_input(include_str!("day25.testinput"));
+        let wires = mkgraph(&input).edges().count();
+        let reversed = input.iter()
+            .flat_map(|i| i.connections.iter().map(|j| Input{comp: j.clone(), connections: HashSet::from([i.comp.clone()])}))
+            .collect::<Vec<_>>();
+        input.extend(reversed);
+        assert_eq!(mkgraph(&input).edges().count(), wires);
+        assert_eq!(part1(&input, &Context::default()), Ok(54));
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::HashSet;
+use rand::{Rng, SeedableRng};
+pub use rand::rngs::StdRng;
+
+pub fn rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// A `width` by `height` grid with each cell chosen by `cell`.
+pub fn grid<F>(rng: &mut StdRng, width: usize, height: usize, mut cell: F) -> Vec<String>
+where F: FnMut(&mut StdRng) -> char {
+    (0..height)
+        .map(|_| (0..width).map(|_| cell(rng)).collect())
+        .collect()
+}
+
+/// `count` distinct names of `len` characters drawn from `alphabet`.
+pub fn names(rng: &mut StdRng, count: usize, len: usize, alphabet: &[u8]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut names = Vec::with_capacity(count);
+    while names.len() < count {
+        let name: String = (0..len)
+            .map(|_| alphabet[rng.gen_range(0..alphabet.len())] as char)
+            .collect();
+        if seen.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// The outline of a random skyline of `columns` columns, as a closed
+/// loop of `(direction, length)` moves with directions `U`, `R`, `D` and
+/// `L`. It starts and ends at the bottom left corner, and never touches
+/// itself.
+pub fn skyline(rng: &mut StdRng, columns: usize, max_width: i64, max_height: i64) -> Vec<(char, i64)> {
+    let mut moves = Vec::new();
+    let mut height = rng.gen_range(1..=max_height);
+    let mut total_width = 0;
+    moves.push(('U', height));
+    for col in 0..columns {
+        let width = rng.gen_range(1..=max_width);
+        total_width += width;
+        moves.push(('R', width));
+        let next = if col + 1 == columns { 0 } else {
+            let mut h = height;
+            while h == height {
+                h = rng.gen_range(1..=max_height);
+            }
+            h
+        };
+        moves.push(if next > height { ('U', next - height) } else { ('D', height - next) });
+        height = next;
+    }
+    moves.push(('L', total_width));
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn gen_test() {
+        for day in days::all() {
+            if let Some(lines) = day.generate(&mut rng(1), Some(12)) {
+                assert!(day.parse(lines).is_ok(), "day {} generated bad input", day.day());
+            }
+        }
+        let moves = skyline(&mut rng(1), 5, 3, 3);
+        assert_eq!(moves.len(), 12);
+        assert_eq!(moves.iter().filter(|(d, _)| *d == 'U').map(|m| m.1).sum::<i64>(),
+            moves.iter().filter(|(d, _)| *d == 'D').map(|m| m.1).sum::<i64>());
+    }
+}
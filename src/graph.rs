@@ -0,0 +1,316 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io::{self, Write};
+use std::ops::Add;
+
+/// A graph over nodes of type `N`, with edge weights of type `W` (`()` for
+/// an unweighted graph). Parallel edges are allowed. Nodes keep the order
+/// they were first added in, so traversals and output are repeatable.
+#[derive(Clone, Debug)]
+pub struct Graph<N, W = ()> {
+    directed: bool,
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    out: Vec<Vec<(usize, W)>>,
+    // incoming edges; only kept for directed graphs
+    inc: Vec<Vec<(usize, W)>>,
+}
+
+impl<N: Clone + Eq + Hash, W: Clone> Graph<N, W> {
+    pub fn directed() -> Self {
+        Self::new(true)
+    }
+
+    pub fn undirected() -> Self {
+        Self::new(false)
+    }
+
+    fn new(directed: bool) -> Self {
+        Self { directed, nodes: Vec::new(), index: HashMap::new(), out: Vec::new(), inc: Vec::new() }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item=&N> {
+        self.nodes.iter()
+    }
+
+    pub fn contains_node(&self, n: &N) -> bool {
+        self.index.contains_key(n)
+    }
+
+    /// Adds `n` if it isn't already there.
+    pub fn add_node(&mut self, n: N) {
+        self.id(n);
+    }
+
+    fn id(&mut self, n: N) -> usize {
+        if let Some(i) = self.index.get(&n) {
+            return *i;
+        }
+        let i = self.nodes.len();
+        self.index.insert(n.clone(), i);
+        self.nodes.push(n);
+        self.out.push(Vec::new());
+        self.inc.push(Vec::new());
+        i
+    }
+
+    /// Adds an edge, and its ends if they're new.
+    pub fn add_edge(&mut self, a: N, b: N, weight: W) {
+        let (a, b) = (self.id(a), self.id(b));
+        if self.directed {
+            self.inc[b].push((a, weight.clone()));
+        }
+        else if a != b {
+            self.out[b].push((a, weight.clone()));
+        }
+        self.out[a].push((b, weight));
+    }
+
+    /// Removes one edge from `a` to `b`, returning its weight.
+    pub fn remove_edge(&mut self, a: &N, b: &N) -> Option<W> {
+        let (a, b) = (*self.index.get(a)?, *self.index.get(b)?);
+        let pos = self.out[a].iter().position(|(t, _)| *t == b)?;
+        let (_, weight) = self.out[a].remove(pos);
+        let back = if self.directed { &mut self.inc[b] } else { &mut self.out[b] };
+        if let Some(pos) = back.iter().position(|(t, _)| *t == a) {
+            if self.directed || a != b {
+                back.remove(pos);
+            }
+        }
+        Some(weight)
+    }
+
+    pub fn contains_edge(&self, a: &N, b: &N) -> bool {
+        self.weight(a, b).is_some()
+    }
+
+    /// Weight of the (first) edge from `a` to `b`.
+    pub fn weight(&self, a: &N, b: &N) -> Option<&W> {
+        let (a, b) = (*self.index.get(a)?, *self.index.get(b)?);
+        self.out[a].iter().find(|(t, _)| *t == b).map(|(_, w)| w)
+    }
+
+    /// Nodes that `n` has edges to, with the edge weights.
+    pub fn neighbors(&self, n: &N) -> impl Iterator<Item=(&N, &W)> {
+        self.index.get(n).into_iter()
+            .flat_map(|i| self.out[*i].iter().map(|(t, w)| (&self.nodes[*t], w)))
+    }
+
+    /// Nodes that have edges to `n`, with the edge weights.
+    pub fn predecessors(&self, n: &N) -> impl Iterator<Item=(&N, &W)> {
+        let adj = if self.directed { &self.inc } else { &self.out };
+        self.index.get(n).into_iter()
+            .flat_map(|i| adj[*i].iter().map(|(t, w)| (&self.nodes[*t], w)))
+    }
+
+    /// Every edge, once.
+    pub fn edges(&self) -> impl Iterator<Item=(&N, &N, &W)> {
+        self.out.iter().enumerate()
+            .flat_map(|(a, adj)| adj.iter().map(move |(b, w)| (a, *b, w)))
+            .filter(|(a, b, _)| self.directed || a <= b)
+            .map(|(a, b, w)| (&self.nodes[a], &self.nodes[b], w))
+    }
+
+    // For connectivity a directed edge counts in both directions.
+    fn linked(&self, i: usize) -> impl Iterator<Item=usize> + '_ {
+        let inc: &[(usize, W)] = if self.directed { &self.inc[i] } else { &[] };
+        self.out[i].iter().chain(inc).map(|(t, _)| *t)
+    }
+
+    fn search(&self, start: &N, depth_first: bool) -> (Vec<usize>, Vec<Option<usize>>) {
+        let mut order = Vec::new();
+        let mut prev = vec![None; self.nodes.len()];
+        let mut seen = vec![false; self.nodes.len()];
+        let Some(&start) = self.index.get(start) else {
+            return (order, prev);
+        };
+        let mut queue = VecDeque::from([start]);
+        seen[start] = !depth_first;
+        while let Some(i) = if depth_first { queue.pop_back() } else { queue.pop_front() } {
+            if depth_first {
+                if seen[i] { continue; }
+                seen[i] = true;
+            }
+            order.push(i);
+            for (t, _) in &self.out[i] {
+                if !seen[*t] {
+                    if !depth_first {
+                        seen[*t] = true;
+                        prev[*t] = Some(i);
+                    }
+                    queue.push_back(*t);
+                }
+            }
+        }
+        (order, prev)
+    }
+
+    /// Nodes reachable from `start` in breadth first order.
+    pub fn bfs(&self, start: &N) -> Vec<&N> {
+        self.search(start, false).0.into_iter().map(|i| &self.nodes[i]).collect()
+    }
+
+    /// Nodes reachable from `start` in depth first order.
+    pub fn dfs(&self, start: &N) -> Vec<&N> {
+        self.search(start, true).0.into_iter().map(|i| &self.nodes[i]).collect()
+    }
+
+    /// A path from `from` to `to` with the fewest edges.
+    pub fn path(&self, from: &N, to: &N) -> Option<Vec<&N>> {
+        let to = *self.index.get(to)?;
+        let (order, prev) = self.search(from, false);
+        order.contains(&to).then(|| self.unwind(to, &prev))
+    }
+
+    fn unwind(&self, to: usize, prev: &[Option<usize>]) -> Vec<&N> {
+        let mut path = vec![to];
+        while let Some(p) = prev[*path.last().unwrap()] {
+            path.push(p);
+        }
+        path.into_iter().rev().map(|i| &self.nodes[i]).collect()
+    }
+
+    /// The connected components (weakly connected, for a directed graph).
+    pub fn components(&self) -> Vec<Vec<&N>> {
+        let mut component = vec![usize::MAX; self.nodes.len()];
+        let mut out: Vec<Vec<&N>> = Vec::new();
+        for start in 0..self.nodes.len() {
+            if component[start] != usize::MAX { continue; }
+            let mut members = Vec::new();
+            let mut stack = vec![start];
+            component[start] = out.len();
+            while let Some(i) = stack.pop() {
+                members.push(&self.nodes[i]);
+                for t in self.linked(i) {
+                    if component[t] == usize::MAX {
+                        component[t] = out.len();
+                        stack.push(t);
+                    }
+                }
+            }
+            out.push(members);
+        }
+        out
+    }
+
+    /// Writes the graph in Graphviz DOT format.
+    pub fn write_dot<O: Write>(&self, out: &mut O) -> io::Result<()>
+    where N: Display {
+        self.write_dot_with(out, |_| String::new(), |_, _, _| String::new())
+    }
+
+    /// Writes the graph in Graphviz DOT format, with attributes (such as
+    /// `shape=box` or `label=3`) for each node and edge.
+    pub fn write_dot_with<O, FN, FE>(&self, out: &mut O, node_attrs: FN, edge_attrs: FE) -> io::Result<()>
+    where
+        O: Write,
+        N: Display,
+        FN: Fn(&N) -> String,
+        FE: Fn(&N, &N, &W) -> String,
+    {
+        fn id<N: Display>(n: &N) -> String {
+            format!("\"{}\"", n.to_string().replace('"', "\\\""))
+        }
+        fn attrs(a: String) -> String {
+            if a.is_empty() { a } else { format!(" [{a}]") }
+        }
+        let (kind, arrow) = if self.directed { ("digraph", "->") } else { ("graph", "--") };
+        writeln!(out, "{kind} G {{")?;
+        for n in &self.nodes {
+            writeln!(out, "  {}{};", id(n), attrs(node_attrs(n)))?;
+        }
+        for (a, b, w) in self.edges() {
+            writeln!(out, "  {} {arrow} {}{};", id(a), id(b), attrs(edge_attrs(a, b, w)))?;
+        }
+        writeln!(out, "}}")
+    }
+
+    /// [`write_dot_with`](Self::write_dot_with), to a string.
+    pub fn to_dot_with<FN, FE>(&self, node_attrs: FN, edge_attrs: FE) -> String
+    where
+        N: Display,
+        FN: Fn(&N) -> String,
+        FE: Fn(&N, &N, &W) -> String,
+    {
+        let mut out = Vec::new();
+        self.write_dot_with(&mut out, node_attrs, edge_attrs).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+}
+
+impl<N, W> Graph<N, W>
+where
+    N: Clone + Eq + Hash,
+    W: Copy + Ord + Default + Add<Output=W>,
+{
+    /// The least total weight of a path from `from` to `to`, and the path.
+    pub fn shortest_path(&self, from: &N, to: &N) -> Option<(W, Vec<&N>)> {
+        let (from, to) = (*self.index.get(from)?, *self.index.get(to)?);
+        let mut cost: Vec<Option<W>> = vec![None; self.nodes.len()];
+        let mut prev = vec![None; self.nodes.len()];
+        let mut queue = BinaryHeap::from([Reverse((W::default(), from))]);
+        cost[from] = Some(W::default());
+        while let Some(Reverse((c, i))) = queue.pop() {
+            if i == to {
+                return Some((c, self.unwind(to, &prev)));
+            }
+            if cost[i].is_some_and(|best| c > best) { continue; }
+            for (t, w) in &self.out[i] {
+                let nc = c + *w;
+                if cost[*t].is_none_or(|best| nc < best) {
+                    cost[*t] = Some(nc);
+                    prev[*t] = Some(i);
+                    queue.push(Reverse((nc, *t)));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph_test() {
+        let mut g: Graph<&str, usize> = Graph::directed();
+        for (a, b, w) in [("a", "b", 1), ("b", "c", 1), ("a", "c", 5), ("c", "d", 1), ("e", "d", 1)] {
+            g.add_edge(a, b, w);
+        }
+        g.add_node("f");
+        assert_eq!(g.bfs(&"a"), [&"a", &"b", &"c", &"d"]);
+        assert_eq!(g.dfs(&"a"), [&"a", &"c", &"d", &"b"]);
+        assert_eq!(g.path(&"a", &"d"), Some(vec![&"a", &"c", &"d"]));
+        assert_eq!(g.path(&"d", &"a"), None);
+        assert_eq!(g.shortest_path(&"a", &"d"), Some((3, vec![&"a", &"b", &"c", &"d"])));
+        assert_eq!(g.predecessors(&"d").map(|(n, _)| *n).collect::<Vec<_>>(), ["c", "e"]);
+        assert_eq!(g.components().len(), 2);
+
+        let mut u: Graph<&str> = Graph::undirected();
+        u.add_edge("a", "b", ());
+        u.add_edge("b", "c", ());
+        assert_eq!(u.path(&"c", &"a"), Some(vec![&"c", &"b", &"a"]));
+        assert_eq!(u.remove_edge(&"b", &"a"), Some(()));
+        assert_eq!(u.components().len(), 2);
+
+        let mut dot = Vec::new();
+        u.write_dot(&mut dot).unwrap();
+        assert_eq!(String::from_utf8(dot).unwrap(), "graph G {\n  \"a\";\n  \"b\";\n  \"c\";\n  \"b\" -- \"c\";\n}\n");
+    }
+}
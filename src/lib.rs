@@ -0,0 +1,28 @@
+//! Advent of Code 2023 solutions.
+//!
+//! Each day lives in its own module under [`days`], with its input types,
+//! `part1` and `part2` functions and the types they're built from. Every
+//! day also implements [`runner::Day`], which is how the `aoc` and `dayNN`
+//! binaries find and run it.
+//!
+//! ```
+//! use advent2023::days::day12;
+//! use advent2023::runner::{Context, Day};
+//!
+//! let lines = vec!["???.### 1,1,3".to_string()];
+//! let input = day12::Day12.parse(lines).unwrap();
+//! assert_eq!(day12::part1(&input, &Context::default()).unwrap(), 1);
+//! ```
+
+pub mod bignum;
+pub mod budget;
+pub mod days;
+pub mod gen;
+pub mod graph;
+pub mod memory;
+pub mod parse;
+pub mod render;
+pub mod runner;
+pub mod snapshot;
+pub mod trace;
+pub mod verify;
@@ -0,0 +1,105 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Whether heap usage is being counted (the `alloc-stats` feature).
+pub const ENABLED: bool = cfg!(feature = "alloc-stats");
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static IN_USE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting allocations and the bytes in use as it
+/// goes. With the `alloc-stats` feature it's the global allocator.
+pub struct Counting;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn grow(bytes: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    let now = IN_USE.fetch_add(bytes, Ordering::Relaxed) + bytes;
+    PEAK.fetch_max(now, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+            grow(new_size);
+        }
+        new
+    }
+}
+
+/// Heap used while running something.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Usage {
+    /// Allocations (and reallocations) made.
+    pub allocations: usize,
+    /// Most bytes in use at once, over what was in use at the start.
+    pub peak: usize,
+}
+
+/// Runs `f`, and reports its heap usage. The counts are for the whole
+/// process, so anything running alongside `f` (other days with `--jobs`,
+/// say) is counted too. Without the `alloc-stats` feature they're zero.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let base = IN_USE.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let out = f();
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(base),
+    };
+    (out, usage)
+}
+
+/// `bytes` in B, KiB, MiB or GiB, whichever reads best.
+pub fn human(bytes: usize) -> String {
+    let mut n = bytes as f64;
+    for unit in ["B", "KiB", "MiB"] {
+        if n < 1024.0 {
+            return if unit == "B" { format!("{bytes} B") } else { format!("{n:.1} {unit}") };
+        }
+        n /= 1024.0;
+    }
+    format!("{n:.1} GiB")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_test() {
+        assert_eq!(human(512), "512 B");
+        assert_eq!(human(3 << 20), "3.0 MiB");
+        let (v, usage) = measure(|| vec![0u8; 1 << 20].len());
+        assert_eq!(v, 1 << 20);
+        if ENABLED {
+            // other tests run alongside, so only the count is certain
+            assert!(usage.allocations >= 1);
+        }
+    }
+}
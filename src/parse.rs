@@ -0,0 +1,248 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+use std::vec::Vec;
+use ya_advent_lib::grid::Grid;
+
+const EOL: &[char] = &['\n', '\r'];
+
+/// A malformed piece of puzzle input: where it was, what was there,
+/// and what should have been there instead.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// 1-based line number, once known.
+    pub line: Option<usize>,
+    /// 1-based column within the line.
+    pub column: usize,
+    pub token: String,
+    pub expected: String,
+}
+
+/// Byte offset of `inner` within `outer`. `inner` is normally a slice
+/// of `outer`; failing that it's searched for.
+fn offset_in(outer: &str, inner: &str) -> usize {
+    let start = outer.as_ptr() as usize;
+    let ptr = inner.as_ptr() as usize;
+    if ptr >= start && ptr + inner.len() <= start + outer.len() {
+        ptr - start
+    }
+    else {
+        outer.find(inner).unwrap_or(0)
+    }
+}
+
+impl ParseError {
+    /// An error at `token`, a slice of the line `s`.
+    pub fn at(s: &str, token: &str, expected: &str) -> Self {
+        Self {
+            line: None,
+            column: offset_in(s, token) + 1,
+            token: token.into(),
+            expected: expected.into(),
+        }
+    }
+
+    /// An error for a line `s` that ended before `expected` was found.
+    pub fn missing(s: &str, expected: &str) -> Self {
+        Self {
+            line: None,
+            column: s.len() + 1,
+            token: String::new(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Adjusts an error from parsing `inner`, a slice of `outer`, to be
+    /// relative to `outer`.
+    pub fn within(mut self, outer: &str, inner: &str) -> Self {
+        self.column += offset_in(outer, inner);
+        self
+    }
+
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {line}, ")?;
+        }
+        write!(f, "column {}: expected {}, ", self.column, self.expected)?;
+        if self.token.is_empty() {
+            write!(f, "found end of line")
+        }
+        else {
+            write!(f, "found `{}`", self.token)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<Infallible> for ParseError {
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
+}
+
+/// Parses `token`, a slice of the line `s`, as a `T`.
+pub fn parse_token<T: FromStr>(s: &str, token: &str, expected: &str) -> Result<T, ParseError> {
+    token.parse::<T>().map_err(|_| ParseError::at(s, token, expected))
+}
+
+/// Takes the next token of the line `s` from `itr`.
+pub fn next_token<'a, I>(s: &str, itr: &mut I, expected: &str) -> Result<&'a str, ParseError>
+where I: Iterator<Item=&'a str> {
+    itr.next().ok_or_else(|| ParseError::missing(s, expected))
+}
+
+fn parse_line<T>(line: &str, line_num: usize) -> Result<T, ParseError>
+where T: FromStr, T::Err: Into<ParseError> {
+    line.trim_end_matches(EOL)
+        .parse::<T>()
+        .map_err(|e| e.into().with_line(line_num))
+}
+
+/// Parses every line as a `T`.
+pub fn parse_lines<T, I>(lines: I) -> Result<Vec<T>, ParseError>
+where T: FromStr, T::Err: Into<ParseError>, I: IntoIterator<Item=String> {
+    lines.into_iter()
+        .enumerate()
+        .map(|(idx, l)| parse_line(&l, idx + 1))
+        .collect()
+}
+
+/// Parses lines as groups of `T` separated by blank lines.
+pub fn parse_grouped<T, I>(lines: I) -> Result<Vec<Vec<T>>, ParseError>
+where T: FromStr, T::Err: Into<ParseError>, I: IntoIterator<Item=String> {
+    let mut data: Vec<Vec<T>> = Vec::new();
+    let mut row: Vec<T> = Vec::new();
+    for (idx, line) in lines.into_iter().enumerate() {
+        let val = line.trim_end_matches(EOL);
+        if val.is_empty() {
+            data.push(row);
+            row = Vec::new();
+        }
+        else {
+            row.push(parse_line(val, idx + 1)?);
+        }
+    }
+    if !row.is_empty() {
+        data.push(row);
+    }
+    Ok(data)
+}
+
+/// Parses lines up to the first blank line as `T1`, and the rest as `T2`.
+pub fn parse_sectioned<T1, T2, I>(lines: I) -> Result<(Vec<T1>, Vec<T2>), ParseError>
+where
+    T1: FromStr, T1::Err: Into<ParseError>,
+    T2: FromStr, T2::Err: Into<ParseError>,
+    I: IntoIterator<Item=String>,
+{
+    let mut data1: Vec<T1> = Vec::new();
+    let mut data2: Vec<T2> = Vec::new();
+    let mut itr = lines.into_iter().enumerate();
+
+    for (idx, l) in itr.by_ref() {
+        let l = l.trim_end_matches(EOL);
+        if l.is_empty() { break; }
+        data1.push(parse_line(l, idx + 1)?);
+    }
+    for (idx, l) in itr {
+        data2.push(parse_line(&l, idx + 1)?);
+    }
+    Ok((data1, data2))
+}
+
+/// Checks that every character of the grid `lines` is one a `T` can be
+/// made from, which `expected` lists.
+pub fn check_grid<T: TryFrom<char>>(lines: &[String], expected: &str) -> Result<(), ParseError> {
+    for (idx, s) in lines.iter().enumerate() {
+        if let Some((col, c)) = s.char_indices().find(|(_, c)| T::try_from(*c).is_err()) {
+            return Err(ParseError::at(s, &s[col .. col + c.len_utf8()], expected).with_line(idx + 1));
+        }
+    }
+    Ok(())
+}
+
+/// Where the only `c` in the grid `lines` is, as (x, y). Finding none, or
+/// a second, is an error.
+pub fn find_one(lines: &[String], c: char, expected: &str) -> Result<(i64, i64), ParseError> {
+    let mut found = None;
+    for (y, s) in lines.iter().enumerate() {
+        for (x, token) in s.match_indices(c) {
+            if found.is_some() {
+                return Err(ParseError::at(s, token, expected).with_line(y + 1));
+            }
+            found = Some((x as i64, y as i64));
+        }
+    }
+    found.ok_or_else(|| {
+        ParseError::missing(lines.last().map_or("", String::as_str), expected).with_line(lines.len().max(1))
+    })
+}
+
+/// The grid `lines` as `T`s, once `check_grid` has passed them.
+pub fn grid<T: Copy + TryFrom<char>>(lines: &[String], default: T, padding: i64) -> Grid<T> {
+    Grid::from_input_map(lines, default, padding, |c| {
+        T::try_from(c).unwrap_or_else(|_| panic!("`{c}` got past check_grid"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Pair(u32, u32);
+
+    impl FromStr for Pair {
+        type Err = ParseError;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let mut itr = s.split(',');
+            let a = parse_token(s, next_token(s, &mut itr, "a number")?, "a number")?;
+            let b = parse_token(s, next_token(s, &mut itr, "a number")?, "a number")?;
+            Ok(Pair(a, b))
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    struct Bit;
+
+    impl TryFrom<char> for Bit {
+        type Error = char;
+        fn try_from(c: char) -> Result<Self, char> {
+            if c == '.' || c == '#' { Ok(Bit) } else { Err(c) }
+        }
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let lines = ["1,2", "3,x4", "5"].map(String::from);
+        let err = parse_lines::<Pair, _>(lines.clone()).err().unwrap();
+        assert_eq!(err.line, Some(2));
+        assert_eq!(err.column, 3);
+        assert_eq!(err.token, "x4");
+        assert_eq!(err.to_string(), "line 2, column 3: expected a number, found `x4`");
+
+        let err = parse_lines::<Pair, _>([lines[0].clone(), lines[2].clone()]).err().unwrap();
+        assert_eq!(err.to_string(), "line 2, column 2: expected a number, found end of line");
+
+        let grid = ["..#", ".X."].map(String::from);
+        let err = check_grid::<Bit>(&grid, "`.` or `#`").err().unwrap();
+        assert_eq!(err.to_string(), "line 2, column 2: expected `.` or `#`, found `X`");
+        assert!(check_grid::<Bit>(&grid[..1], "`.` or `#`").is_ok());
+        assert_eq!(find_one(&grid, '#', "one `#`"), Ok((2, 0)));
+        let err = find_one(&grid, 'S', "one `S`").err().unwrap();
+        assert_eq!(err.to_string(), "line 2, column 4: expected one `S`, found end of line");
+        let err = find_one(&grid, '.', "one `.`").err().unwrap();
+        assert_eq!(err.to_string(), "line 1, column 2: expected one `.`, found `.`");
+
+        let groups = parse_grouped::<Pair, _>(["1,2", "", "3,4", "5,6"].map(String::from)).unwrap();
+        assert_eq!(groups.iter().map(|g| g.len()).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(groups[1][1].0 + groups[1][1].1, 11);
+    }
+}
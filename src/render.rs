@@ -0,0 +1,179 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use ya_advent_lib::coords::Coord2D;
+use ya_advent_lib::grid::Grid;
+
+/// Pixels per cell in PPM output, so that a puzzle grid isn't tiny.
+const PPM_SCALE: usize = 4;
+/// Size of a cell in SVG output.
+const SVG_CELL: usize = 10;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GRAY: Rgb = Rgb(128, 128, 128);
+    pub const DARK_GRAY: Rgb = Rgb(48, 48, 48);
+    pub const RED: Rgb = Rgb(220, 40, 40);
+    pub const GREEN: Rgb = Rgb(40, 200, 60);
+    pub const BLUE: Rgb = Rgb(50, 90, 230);
+    pub const YELLOW: Rgb = Rgb(250, 220, 40);
+    pub const ORANGE: Rgb = Rgb(250, 140, 20);
+}
+
+/// A picture of a grid, one colored cell per grid cell. Cells are
+/// addressed in the coordinates of the grid it was made from.
+#[derive(Clone, Debug)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    origin: Coord2D,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self { width, height, origin: Coord2D::new(0, 0), pixels: vec![background; width * height] }
+    }
+
+    /// An image of `grid`, leaving out any padding, with each cell
+    /// colored by `color`.
+    pub fn from_grid<T: Copy, F: Fn(T) -> Rgb>(grid: &Grid<T>, color: F) -> Self {
+        let (xb, yb) = (grid.x_bounds_orig(), grid.y_bounds_orig());
+        let mut image = Self::new(xb.clone().count(), yb.clone().count(), Rgb::BLACK);
+        image.origin = Coord2D::new(xb.start, yb.start);
+        for y in yb {
+            for x in xb.clone() {
+                image.set(Coord2D::new(x, y), color(grid.get(x, y)));
+            }
+        }
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, c: Coord2D) -> Option<usize> {
+        let (x, y) = (c.x - self.origin.x, c.y - self.origin.y);
+        (x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height)
+            .then(|| y as usize * self.width + x as usize)
+    }
+
+    /// Colors one cell. Cells outside the image are ignored.
+    pub fn set(&mut self, c: Coord2D, color: Rgb) {
+        if let Some(idx) = self.index(c) {
+            self.pixels[idx] = color;
+        }
+    }
+
+    /// Colors each of `cells`, e.g. a path or the cells a solver visited.
+    pub fn overlay<I: IntoIterator<Item=Coord2D>>(&mut self, cells: I, color: Rgb) {
+        for c in cells {
+            self.set(c, color);
+        }
+    }
+
+    /// Copies `other` into this image with its top left corner at `at`.
+    pub fn draw(&mut self, at: Coord2D, other: &Image) {
+        for y in 0..other.height {
+            for x in 0..other.width {
+                let color = other.pixels[y * other.width + x];
+                self.set(at + Coord2D::new(x as i64, y as i64), color);
+            }
+        }
+    }
+
+    fn row(&self, y: usize) -> &[Rgb] {
+        &self.pixels[y * self.width .. (y + 1) * self.width]
+    }
+
+    pub fn write_ppm<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width * PPM_SCALE, self.height * PPM_SCALE)?;
+        for y in 0..self.height {
+            let line: Vec<u8> = self.row(y).iter()
+                .flat_map(|p| [[p.0, p.1, p.2]; PPM_SCALE])
+                .flatten()
+                .collect();
+            for _ in 0..PPM_SCALE {
+                out.write_all(&line)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes an SVG with a rectangle for each run of same-colored cells
+    /// in a row.
+    pub fn write_svg<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" shape-rendering="crispEdges">"#,
+            w = self.width * SVG_CELL,
+            h = self.height * SVG_CELL,
+        )?;
+        for y in 0..self.height {
+            let row = self.row(y);
+            let mut x = 0;
+            while x < row.len() {
+                let run = row[x..].iter().take_while(|p| **p == row[x]).count();
+                let Rgb(r, g, b) = row[x];
+                writeln!(
+                    out,
+                    r##"<rect x="{}" y="{}" width="{}" height="{SVG_CELL}" fill="#{r:02x}{g:02x}{b:02x}"/>"##,
+                    x * SVG_CELL,
+                    y * SVG_CELL,
+                    run * SVG_CELL,
+                )?;
+                x += run;
+            }
+        }
+        writeln!(out, "</svg>")
+    }
+
+    /// Writes the image to `path`, as SVG or PPM according to its
+    /// extension.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let svg = match path.extension().and_then(|e| e.to_str()) {
+            Some("svg") => true,
+            Some("ppm") => false,
+            _ => {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "image file must end in .ppm or .svg"));
+            },
+        };
+        let mut out = BufWriter::new(File::create(path)?);
+        if svg { self.write_svg(&mut out)? } else { self.write_ppm(&mut out)? }
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_test() {
+        let grid: Grid<char> = Grid::from_input(&["#.".into(), "..".into()], '.', 1);
+        let mut image = Image::from_grid(&grid, |c| if c == '#' { Rgb::WHITE } else { Rgb::BLACK });
+        image.overlay([Coord2D::new(1, 1), Coord2D::new(5, 5)], Rgb::RED);
+        assert_eq!((image.width(), image.height()), (2, 2));
+
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        let header = format!("P6\n{0} {0}\n255\n", 2 * PPM_SCALE);
+        assert_eq!(ppm.len(), header.len() + 3 * 4 * PPM_SCALE * PPM_SCALE);
+        assert_eq!(&ppm[header.len() .. header.len() + 3], &[255, 255, 255]);
+
+        let mut svg = Vec::new();
+        image.write_svg(&mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert_eq!(svg.matches("<rect").count(), 4);
+        assert!(svg.contains(r##"x="10" y="10" width="10" height="10" fill="#dc2828""##));
+    }
+}
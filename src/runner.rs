@@ -0,0 +1,1011 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use json::{object, JsonValue};
+use num::BigInt;
+use crate::bignum::Int;
+use crate::budget::{Budget, OutOfBudget};
+use crate::days;
+use crate::gen::StdRng;
+use crate::memory::{self, Usage};
+use crate::parse::ParseError;
+use crate::render::Image;
+use crate::trace::Trace;
+
+/// The answer to one part of a puzzle.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Answer {
+    Int(i128),
+    /// An answer too big for an `i128`.
+    Big(BigInt),
+    /// The part's search ran out of budget before finding the answer.
+    Unsolved(OutOfBudget),
+    /// The part has no answer (e.g. day 25 part 2).
+    None,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Big(n) => write!(f, "{n}"),
+            Answer::Unsolved(e) => write!(f, "{e}"),
+            Answer::None => write!(f, "-"),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer::Int(value as i128)
+            }
+        })*
+    };
+}
+answer_from_int!(u32, u64, usize, i32, i64);
+
+impl<T: Into<Answer>> From<Result<T, OutOfBudget>> for Answer {
+    fn from(value: Result<T, OutOfBudget>) -> Self {
+        value.map_or_else(Answer::Unsolved, T::into)
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Answer::None, T::into)
+    }
+}
+
+impl From<Int> for Answer {
+    fn from(value: Int) -> Self {
+        match value {
+            Int::Small(n) => Answer::Int(n),
+            Int::Big(n) => Answer::Big(n),
+        }
+    }
+}
+
+/// Something a solver takes for granted about its input, and whether
+/// the input bears it out.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Assumption {
+    pub what: &'static str,
+    /// What the input has instead, if the assumption doesn't hold.
+    pub failure: Option<String>,
+}
+
+impl Assumption {
+    /// `found` is an error saying what the input has instead, when it
+    /// doesn't hold.
+    pub fn check(what: &'static str, found: Result<(), String>) -> Self {
+        Self { what, failure: found.err() }
+    }
+
+    pub fn holds(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// A puzzle constant that can be changed with `--param NAME=VALUE`, to
+/// see what the answer would be for a different puzzle.
+#[derive(Clone, Copy, Debug)]
+pub struct Param {
+    pub name: &'static str,
+    /// The value the puzzle gives.
+    pub default: i64,
+    /// The least value that makes sense.
+    pub min: i64,
+    /// Values must be `min` plus a multiple of this.
+    pub step: i64,
+    pub help: &'static str,
+}
+
+/// A puzzle setting that isn't a number, changed with `--param
+/// NAME=VALUE` like a [`Param`].
+#[derive(Clone, Copy, Debug)]
+pub struct TextParam {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub help: &'static str,
+    /// Says what's wrong with a value, if anything.
+    pub check: fn(&str) -> Result<(), String>,
+}
+
+/// The number a `--param` value gives, allowing `_` separators.
+fn param_value(value: &str) -> Option<i64> {
+    value.replace('_', "").parse().ok()
+}
+
+/// Stack size for worker threads; some solvers recurse deeply.
+pub const STACK_SIZE: usize = 64 << 20;
+
+/// Settings that affect how a day's parts are solved.
+#[derive(Clone, Debug, Default)]
+pub struct Context {
+    /// Spread independent work within a part across threads.
+    pub parallel: bool,
+    /// Use the slow but obvious reference version of a solver's clever
+    /// parts, where a day has one. These can be far too slow for a full
+    /// puzzle input.
+    pub reference: bool,
+    /// Where simulation events go, when tracing.
+    pub trace: Option<Arc<Trace>>,
+    /// Limits on open-ended searches.
+    pub budget: Budget,
+    /// Values given for the days' [`Param`]s and [`TextParam`]s.
+    pub params: BTreeMap<String, String>,
+}
+
+impl Context {
+    /// The value of a puzzle parameter: the one given, or the puzzle's.
+    pub fn param(&self, param: &Param) -> i64 {
+        self.params.get(param.name).and_then(|v| param_value(v)).unwrap_or(param.default)
+    }
+
+    /// The value of a text puzzle parameter: the one given, or the
+    /// puzzle's.
+    pub fn text_param(&self, param: &TextParam) -> &str {
+        self.params.get(param.name).map_or(param.default, String::as_str)
+    }
+
+    /// Records a simulation event when tracing, calling `data` for its
+    /// fields only then. Returns false once the trace has reached its
+    /// last step, when the simulation should give up.
+    pub fn trace<F: FnOnce() -> JsonValue>(&self, event: &str, data: F) -> bool {
+        self.trace.as_ref().is_none_or(|t| t.event(event, data()))
+    }
+
+    /// Whether a simulation was cut short by the trace.
+    pub fn stopped(&self) -> bool {
+        self.trace.as_ref().is_some_and(|t| t.stopped())
+    }
+
+    /// Maps `f` over `items`, in parallel if enabled. Results are in the
+    /// same order as `items`.
+    pub fn map<T, R, F>(&self, items: &[T], f: F) -> Vec<R>
+    where T: Sync, R: Send, F: Fn(&T) -> R + Sync {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get()).min(items.len());
+        if !self.parallel || threads < 2 {
+            return items.iter().map(f).collect();
+        }
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<_>>());
+        thread::scope(|s| {
+            for _ in 0..threads {
+                thread::Builder::new()
+                    .stack_size(STACK_SIZE)
+                    .spawn_scoped(s, || loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        if i >= items.len() {
+                            break;
+                        }
+                        let r = f(&items[i]);
+                        results.lock().unwrap()[i] = Some(r);
+                    })
+                    .unwrap();
+            }
+        });
+        results.into_inner().unwrap().into_iter().map(Option::unwrap).collect()
+    }
+}
+
+/// A day's solution: how to turn the input lines into the parsed input,
+/// and how to solve each part from it.
+pub trait Day {
+    const DAY: u32;
+    /// The puzzle's example inputs (the `dayNN.testinput` files).
+    const EXAMPLES: &'static [&'static str] = &[];
+    /// The puzzle constants that can be changed from the command line.
+    const PARAMS: &'static [Param] = &[];
+    /// The puzzle settings that aren't numbers.
+    const TEXT_PARAMS: &'static [TextParam] = &[];
+    type Input: 'static;
+    fn parse(&self, lines: Vec<String>) -> Result<Self::Input, ParseError>;
+    fn part1(&self, input: &Self::Input, ctx: &Context) -> Answer;
+    fn part2(&self, input: &Self::Input, ctx: &Context) -> Answer;
+    /// Generates a random input. What `size` measures (lines, grid width,
+    /// ...) is up to the day. Returns None if the day has no generator.
+    fn generate(&self, _rng: &mut StdRng, _size: Option<usize>) -> Option<Vec<String>> {
+        None
+    }
+    /// Draws the input, with whatever the solver found overlaid on it.
+    /// Returns None if the day has nothing to draw.
+    fn render(&self, _input: &Self::Input, _ctx: &Context) -> Option<Image> {
+        None
+    }
+    /// The puzzle's graph in Graphviz DOT format. Returns None if the day
+    /// isn't a graph puzzle, or an error if the search for what to mark on
+    /// the graph gave up.
+    fn dot(&self, _input: &Self::Input, _ctx: &Context) -> Option<Result<String, OutOfBudget>> {
+        None
+    }
+    /// Checks the properties of the input that the solver relies on but
+    /// the puzzle doesn't promise. Empty if the day relies on none.
+    fn assumptions(&self, _input: &Self::Input, _ctx: &Context) -> Vec<Assumption> {
+        Vec::new()
+    }
+    /// Intermediate results worth keeping an eye on, as text, for the
+    /// snapshot tests. Returns None if the day takes no snapshot.
+    fn snapshot(&self, _input: &Self::Input, _ctx: &Context) -> Option<String> {
+        None
+    }
+    /// What the solver made of each piece of the input, for auditing
+    /// it: a row for each, as JSON objects with the same fields in the
+    /// same order. Empty if the day has nothing to report.
+    fn diagnostics(&self, _input: &Self::Input, _ctx: &Context) -> Vec<JsonValue> {
+        Vec::new()
+    }
+    /// Solves both parts reading the input a line at a time, for inputs
+    /// too big to hold, telling `progress` how many lines it has read and
+    /// the answers so far. Returns None if the day can't.
+    fn stream(&self, _reader: &mut dyn BufRead, _ctx: &Context, _progress: &mut dyn FnMut(usize, [Answer; 2])) -> Option<io::Result<[Answer; 2]>> {
+        None
+    }
+}
+
+/// Object-safe form of [`Day`], so all days can live in one registry.
+pub trait DynDay: Sync {
+    fn day(&self) -> u32;
+    fn examples(&self) -> &'static [&'static str];
+    fn params(&self) -> &'static [Param];
+    fn text_params(&self) -> &'static [TextParam];
+    fn parse(&self, lines: Vec<String>) -> Result<Box<dyn Any>, ParseError>;
+    fn part1(&self, input: &dyn Any, ctx: &Context) -> Answer;
+    fn part2(&self, input: &dyn Any, ctx: &Context) -> Answer;
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>>;
+    fn render(&self, input: &dyn Any, ctx: &Context) -> Option<Image>;
+    fn dot(&self, input: &dyn Any, ctx: &Context) -> Option<Result<String, OutOfBudget>>;
+    fn assumptions(&self, input: &dyn Any, ctx: &Context) -> Vec<Assumption>;
+    fn snapshot(&self, input: &dyn Any, ctx: &Context) -> Option<String>;
+    fn diagnostics(&self, input: &dyn Any, ctx: &Context) -> Vec<JsonValue>;
+    fn stream(&self, reader: &mut dyn BufRead, ctx: &Context, progress: &mut dyn FnMut(usize, [Answer; 2])) -> Option<io::Result<[Answer; 2]>>;
+}
+
+impl<D: Day + Sync> DynDay for D {
+    fn day(&self) -> u32 {
+        D::DAY
+    }
+    fn examples(&self) -> &'static [&'static str] {
+        D::EXAMPLES
+    }
+    fn params(&self) -> &'static [Param] {
+        D::PARAMS
+    }
+    fn text_params(&self) -> &'static [TextParam] {
+        D::TEXT_PARAMS
+    }
+    fn parse(&self, lines: Vec<String>) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(Day::parse(self, lines)?))
+    }
+    fn part1(&self, input: &dyn Any, ctx: &Context) -> Answer {
+        Day::part1(self, input.downcast_ref().unwrap(), ctx)
+    }
+    fn part2(&self, input: &dyn Any, ctx: &Context) -> Answer {
+        Day::part2(self, input.downcast_ref().unwrap(), ctx)
+    }
+    fn generate(&self, rng: &mut StdRng, size: Option<usize>) -> Option<Vec<String>> {
+        Day::generate(self, rng, size)
+    }
+    fn render(&self, input: &dyn Any, ctx: &Context) -> Option<Image> {
+        Day::render(self, input.downcast_ref().unwrap(), ctx)
+    }
+    fn dot(&self, input: &dyn Any, ctx: &Context) -> Option<Result<String, OutOfBudget>> {
+        Day::dot(self, input.downcast_ref().unwrap(), ctx)
+    }
+    fn assumptions(&self, input: &dyn Any, ctx: &Context) -> Vec<Assumption> {
+        Day::assumptions(self, input.downcast_ref().unwrap(), ctx)
+    }
+    fn snapshot(&self, input: &dyn Any, ctx: &Context) -> Option<String> {
+        Day::snapshot(self, input.downcast_ref().unwrap(), ctx)
+    }
+    fn diagnostics(&self, input: &dyn Any, ctx: &Context) -> Vec<JsonValue> {
+        Day::diagnostics(self, input.downcast_ref().unwrap(), ctx)
+    }
+    fn stream(&self, reader: &mut dyn BufRead, ctx: &Context, progress: &mut dyn FnMut(usize, [Answer; 2])) -> Option<io::Result<[Answer; 2]>> {
+        Day::stream(self, reader, ctx, progress)
+    }
+}
+
+/// Why a day could not be run.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Parse(ParseError),
+    /// A part gave up on its search.
+    Unsolved { part: usize, error: OutOfBudget },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{e}"),
+            Error::Parse(e) => write!(f, "{e}"),
+            Error::Unsolved { part, error } => write!(f, "part {part}: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Error::Io(value)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(value: ParseError) -> Self {
+        Error::Parse(value)
+    }
+}
+
+pub fn default_input_path(day: u32) -> PathBuf {
+    PathBuf::from(format!("day{day:02}.input"))
+}
+
+pub fn read_lines(path: &Path) -> io::Result<Vec<String>> {
+    BufReader::new(File::open(path)?).lines().collect()
+}
+
+/// Where a day's input comes from.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum Source {
+    /// `dayNN.input` in the current directory.
+    #[default]
+    Default,
+    Path(PathBuf),
+    Stdin,
+    /// One of the day's example inputs, numbered from 1.
+    Example(usize),
+}
+
+impl Source {
+    pub fn read(&self, day: &dyn DynDay) -> io::Result<Vec<String>> {
+        match self {
+            Source::Default => read_lines(&default_input_path(day.day())),
+            Source::Path(path) => read_lines(path),
+            Source::Stdin => io::stdin().lock().lines().collect(),
+            Source::Example(n) => day.examples()
+                .get(n.wrapping_sub(1))
+                .map(|s| s.lines().map(String::from).collect())
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such example input")),
+        }
+    }
+
+    /// Opens the input to read a line at a time.
+    pub fn open(&self, day: &dyn DynDay) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Source::Default => Ok(Box::new(BufReader::new(File::open(default_input_path(day.day()))?))),
+            Source::Path(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+            Source::Stdin => Ok(Box::new(io::stdin().lock())),
+            Source::Example(n) => day.examples()
+                .get(n.wrapping_sub(1))
+                .map(|s| Box::new(s.as_bytes()) as Box<dyn BufRead>)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such example input")),
+        }
+    }
+
+    /// Describes the source in messages about `day`.
+    pub fn describe(&self, day: u32) -> String {
+        match self {
+            Source::Default => default_input_path(day).display().to_string(),
+            Source::Path(path) => path.display().to_string(),
+            Source::Stdin => "<stdin>".into(),
+            Source::Example(n) => format!("example {n}"),
+        }
+    }
+}
+
+pub const OPTIONS_USAGE: &str =
+    "[INPUT | - | --example[=N]] [--format text|json] [--bench N [--bench-out FILE]] [--parallel] [--reference] [--render FILE] [--dot FILE] [--trace [--until STEP]] [--max-iterations N] [--timeout SECS] [--check-assumptions] [--diagnose] [--stream] [--param NAME=VALUE]...";
+
+/// How answers are printed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    /// `Part 1: ...` lines.
+    #[default]
+    Text,
+    /// One JSON object per day.
+    Json,
+}
+
+/// Command line options shared by the day binaries and `aoc run`.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    pub source: Source,
+    pub format: Format,
+    /// Run each part this many times and report timings.
+    pub bench: Option<usize>,
+    /// File to append benchmark results to, one JSON object per line.
+    pub bench_out: Option<PathBuf>,
+    pub parallel: bool,
+    pub reference: bool,
+    /// Image file (`.ppm` or `.svg`) to draw the day's grid to.
+    pub render: Option<PathBuf>,
+    /// File to write the day's graph to, in Graphviz DOT format.
+    pub dot: Option<PathBuf>,
+    /// Log simulation events to stderr.
+    pub trace: bool,
+    /// Last trace step to run to.
+    pub until: Option<usize>,
+    /// Limits on open-ended searches.
+    pub budget: Budget,
+    /// Check the input against what the solver assumes about it.
+    pub check_assumptions: bool,
+    /// Report what the solver made of each piece of the input.
+    pub diagnose: bool,
+    /// Solve while reading the input, without holding it all.
+    pub stream: bool,
+    /// Puzzle constants to change.
+    pub params: BTreeMap<String, String>,
+}
+
+impl Options {
+    pub fn parse<I: IntoIterator<Item=String>>(args: I) -> Result<Self, String> {
+        let mut opts = Options::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (name, value) = match arg.split_once('=') {
+                Some((n, v)) if n.starts_with("--") => (n, Some(v.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let source = match name {
+                "-" => Source::Stdin,
+                "--example" => {
+                    match value.as_deref().map_or(Ok(1), |v| v.parse::<usize>()) {
+                        Ok(n) if n > 0 => Source::Example(n),
+                        _ => { return Err(format!("invalid example number in {arg}")); },
+                    }
+                },
+                "--format" => {
+                    opts.format = match value.or_else(|| args.next()).as_deref() {
+                        Some("text") => Format::Text,
+                        Some("json") => Format::Json,
+                        _ => { return Err("--format must be text or json".into()); },
+                    };
+                    continue;
+                },
+                "--bench" => {
+                    match value.or_else(|| args.next()).map(|v| v.parse::<usize>()) {
+                        Some(Ok(n)) if n > 0 => { opts.bench = Some(n); },
+                        _ => { return Err("--bench needs a number of runs".into()); },
+                    }
+                    continue;
+                },
+                "--parallel" => {
+                    opts.parallel = true;
+                    continue;
+                },
+                "--reference" => {
+                    opts.reference = true;
+                    continue;
+                },
+                "--param" => {
+                    let param = value.or_else(|| args.next());
+                    let Some((name, value)) = param.as_deref().and_then(|p| p.split_once('=')) else {
+                        return Err("--param needs NAME=VALUE".into());
+                    };
+                    opts.params.insert(name.to_string(), value.to_string());
+                    continue;
+                },
+                "--check-assumptions" => {
+                    opts.check_assumptions = true;
+                    continue;
+                },
+                "--diagnose" => {
+                    opts.diagnose = true;
+                    continue;
+                },
+                "--stream" => {
+                    opts.stream = true;
+                    continue;
+                },
+                "--trace" => {
+                    opts.trace = true;
+                    continue;
+                },
+                "--until" => {
+                    match value.or_else(|| args.next()).map(|v| v.parse::<usize>()) {
+                        Some(Ok(n)) => { opts.until = Some(n); },
+                        _ => { return Err("--until needs a step number".into()); },
+                    }
+                    continue;
+                },
+                "--max-iterations" => {
+                    match value.or_else(|| args.next()).map(|v| v.parse::<usize>()) {
+                        Some(Ok(n)) if n > 0 => { opts.budget.iterations = Some(n); },
+                        _ => { return Err("--max-iterations needs a number".into()); },
+                    }
+                    continue;
+                },
+                "--timeout" => {
+                    match value.or_else(|| args.next()).map(|v| v.parse::<f64>()) {
+                        Some(Ok(secs)) if secs > 0.0 && secs.is_finite() => {
+                            opts.budget.time = Some(Duration::from_secs_f64(secs));
+                        },
+                        _ => { return Err("--timeout needs a number of seconds".into()); },
+                    }
+                    continue;
+                },
+                "--render" => {
+                    match value.or_else(|| args.next()) {
+                        Some(path) => { opts.render = Some(PathBuf::from(path)); },
+                        None => { return Err("--render needs a file name".into()); },
+                    }
+                    continue;
+                },
+                "--dot" => {
+                    match value.or_else(|| args.next()) {
+                        Some(path) => { opts.dot = Some(PathBuf::from(path)); },
+                        None => { return Err("--dot needs a file name".into()); },
+                    }
+                    continue;
+                },
+                "--bench-out" => {
+                    match value.or_else(|| args.next()) {
+                        Some(path) => { opts.bench_out = Some(PathBuf::from(path)); },
+                        None => { return Err("--bench-out needs a file name".into()); },
+                    }
+                    continue;
+                },
+                a if a.starts_with('-') => {
+                    return Err(format!("unknown option {a}"));
+                },
+                a => Source::Path(PathBuf::from(a)),
+            };
+            if opts.source != Source::Default {
+                return Err("more than one input given".into());
+            }
+            opts.source = source;
+        }
+        if opts.bench_out.is_some() && opts.bench.is_none() {
+            return Err("--bench-out needs --bench".into());
+        }
+        if opts.until.is_some() && !opts.trace {
+            return Err("--until needs --trace".into());
+        }
+        if opts.trace && opts.bench.is_some() {
+            return Err("--trace can't be used with --bench".into());
+        }
+        if opts.stream && (opts.bench.is_some() || opts.trace || opts.render.is_some() || opts.dot.is_some()
+                || opts.check_assumptions || opts.diagnose) {
+            return Err("--stream never holds the whole input, so can't be used with --bench, --trace, --render, --dot, --check-assumptions or --diagnose".into());
+        }
+        Ok(opts)
+    }
+
+    pub fn context(&self) -> Context {
+        Context {
+            parallel: self.parallel,
+            reference: self.reference,
+            trace: self.trace.then(|| Arc::new(Trace::stderr(self.until))),
+            budget: self.budget,
+            params: self.params.clone(),
+        }
+    }
+}
+
+/// The answers for a day, and how long it took to get them.
+#[derive(Clone, Debug)]
+pub struct Solution {
+    pub part1: Answer,
+    pub part2: Answer,
+    pub elapsed: Duration,
+    /// The day's assumptions about the input, if they were checked.
+    pub assumptions: Option<Vec<Assumption>>,
+    /// The day's diagnostics for the input, if asked for.
+    pub diagnostics: Option<Vec<JsonValue>>,
+    /// Heap used by each part, with the `alloc-stats` feature.
+    pub memory: Option<[Usage; 2]>,
+}
+
+/// Checks that `day` has the parameters given, and that their values
+/// make sense.
+fn check_params(day: &dyn DynDay, opts: &Options) -> io::Result<()> {
+    let invalid = |msg| Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+    for (name, value) in &opts.params {
+        if let Some(param) = day.text_params().iter().find(|p| p.name == name) {
+            if let Err(e) = (param.check)(value) {
+                return invalid(format!("parameter {name}: {e}"));
+            }
+            continue;
+        }
+        let Some(param) = day.params().iter().find(|p| p.name == name) else {
+            let known = day.params().iter()
+                .map(|p| format!("{} ({}, default {})", p.name, p.help, p.default))
+                .chain(day.text_params().iter().map(|p| format!("{} ({}, default {})", p.name, p.help, p.default)))
+                .collect::<Vec<_>>();
+            return if known.is_empty() {
+                invalid(format!("no parameter {name}; this day has none"))
+            } else {
+                invalid(format!("no parameter {name}; this day has {}", known.join(", ")))
+            };
+        };
+        let Some(value) = param_value(value) else {
+            return invalid(format!("parameter {name} needs a whole number"));
+        };
+        if value < param.min {
+            return invalid(format!("parameter {name} must be at least {}", param.min));
+        }
+        if (value as i128 - param.min as i128) % param.step as i128 != 0 {
+            return invalid(format!("parameter {name} must be {} plus a multiple of {}", param.min, param.step));
+        }
+    }
+    Ok(())
+}
+
+/// How often `--stream` reports its progress.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Reads the input for `day` and solves both parts.
+pub fn solve(day: &dyn DynDay, opts: &Options) -> Result<Solution, Error> {
+    check_params(day, opts)?;
+    if opts.stream {
+        return solve_stream(day, opts);
+    }
+    let lines = opts.source.read(day)?;
+    let start = Instant::now();
+    let ctx = opts.context();
+    let input = day.parse(lines)?;
+    let assumptions = opts.check_assumptions.then(|| day.assumptions(&*input, &ctx));
+    let diagnostics = opts.diagnose.then(|| day.diagnostics(&*input, &ctx));
+    let (part1, mem1) = memory::measure(|| traced(&ctx, 1, || day.part1(&*input, &ctx)));
+    let part1 = solved(1, part1)?;
+    let (part2, mem2) = memory::measure(|| traced(&ctx, 2, || day.part2(&*input, &ctx)));
+    let part2 = solved(2, part2)?;
+    let elapsed = start.elapsed();
+    export(day, &*input, &ctx, opts)?;
+    let memory = memory::ENABLED.then_some([mem1, mem2]);
+    Ok(Solution { part1, part2, elapsed, assumptions, diagnostics, memory })
+}
+
+/// Solves `day` as its input is read, reporting the answers so far on
+/// stderr every so often.
+fn solve_stream(day: &dyn DynDay, opts: &Options) -> Result<Solution, Error> {
+    let mut reader = opts.source.open(day)?;
+    let ctx = opts.context();
+    let start = Instant::now();
+    let mut reported = start;
+    let mut progress = |lines: usize, [part1, part2]: [Answer; 2]| {
+        // Checking the clock on every line would cost more than the
+        // lines themselves.
+        if lines.is_multiple_of(4096) && reported.elapsed() >= PROGRESS_INTERVAL {
+            eprintln!("day{:02}: {lines} lines: part 1 {part1}, part 2 {part2}", day.day());
+            reported = Instant::now();
+        }
+    };
+    let Some(answers) = day.stream(&mut *reader, &ctx, &mut progress) else {
+        return Err(io::Error::new(io::ErrorKind::Unsupported, "this day can't stream its input").into());
+    };
+    let [part1, part2] = answers?;
+    Ok(Solution {
+        part1: solved(1, part1)?,
+        part2: solved(2, part2)?,
+        elapsed: start.elapsed(),
+        assumptions: None,
+        diagnostics: None,
+        memory: None,
+    })
+}
+
+/// Runs one part, telling the trace which it is. A part the trace cut
+/// short has no answer.
+fn traced<F: FnOnce() -> Answer>(ctx: &Context, part: usize, f: F) -> Answer {
+    if let Some(trace) = &ctx.trace {
+        if trace.stopped() {
+            return Answer::None;
+        }
+        trace.set_part(part);
+    }
+    let answer = f();
+    if ctx.stopped() { Answer::None } else { answer }
+}
+
+/// Turns a part that ran out of budget into an error.
+fn solved(part: usize, answer: Answer) -> Result<Answer, Error> {
+    match answer {
+        Answer::Unsolved(error) => Err(Error::Unsolved { part, error }),
+        answer => Ok(answer),
+    }
+}
+
+fn in_file(path: &Path, e: io::Error) -> io::Error {
+    io::Error::new(e.kind(), format!("{}: {e}", path.display()))
+}
+
+/// Writes whatever `--render` and `--dot` asked for.
+fn export(day: &dyn DynDay, input: &dyn Any, ctx: &Context, opts: &Options) -> Result<(), Error> {
+    if let Some(path) = &opts.render {
+        let image = day.render(input, ctx)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "this day has nothing to render"))?;
+        image.save(path).map_err(|e| in_file(path, e))?;
+    }
+    if let Some(path) = &opts.dot {
+        let dot = day.dot(input, ctx)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "this day has no graph"))?
+            .map_err(|e| io::Error::other(format!("can't draw the graph: {e}")))?;
+        std::fs::write(path, dot).map_err(|e| in_file(path, e))?;
+    }
+    Ok(())
+}
+
+/// Fastest, median and slowest of a set of timed runs.
+#[derive(Clone, Copy, Debug)]
+pub struct Timing {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timing {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Timing {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Runs `f` `runs` times, returning its last result and the timings.
+fn time_runs<T>(runs: usize, mut f: impl FnMut() -> T) -> (T, Timing) {
+    let mut samples = Vec::with_capacity(runs);
+    let mut result = None;
+    for _ in 0..runs {
+        let start = Instant::now();
+        result = Some(f());
+        samples.push(start.elapsed());
+    }
+    (result.unwrap(), Timing::from_samples(samples))
+}
+
+/// Timings for each stage of a day, over `runs` runs.
+#[derive(Clone, Debug)]
+pub struct Bench {
+    pub runs: usize,
+    pub parse: Timing,
+    pub part1: Timing,
+    pub part2: Timing,
+}
+
+/// Like [`solve`], but parses and runs each part `runs` times. The
+/// parts all run against the same parsed input.
+pub fn bench(day: &dyn DynDay, opts: &Options, runs: usize) -> Result<(Solution, Bench), Error> {
+    check_params(day, opts)?;
+    let lines = opts.source.read(day)?;
+    let mut inputs = (0..runs).map(|_| lines.clone()).collect::<Vec<_>>().into_iter();
+    let (input, parse) = time_runs(runs, || day.parse(inputs.next().unwrap()));
+    let input = input?;
+    let ctx = opts.context();
+    let assumptions = opts.check_assumptions.then(|| day.assumptions(&*input, &ctx));
+    let diagnostics = opts.diagnose.then(|| day.diagnostics(&*input, &ctx));
+    let (part1, part1_t) = time_runs(runs, || day.part1(&*input, &ctx));
+    let part1 = solved(1, part1)?;
+    let (part2, part2_t) = time_runs(runs, || day.part2(&*input, &ctx));
+    let part2 = solved(2, part2)?;
+    export(day, &*input, &ctx, opts)?;
+    let elapsed = parse.median + part1_t.median + part2_t.median;
+    Ok((
+        Solution { part1, part2, elapsed, assumptions, diagnostics, memory: None },
+        Bench { runs, parse, part1: part1_t, part2: part2_t },
+    ))
+}
+
+pub fn print_bench(bench: &Bench) {
+    println!("{:<10}{:>11}{:>11}{:>11}", format!("{} runs", bench.runs), "min", "median", "max");
+    for (name, t) in [("parse", &bench.parse), ("part 1", &bench.part1), ("part 2", &bench.part2)] {
+        println!("{name:<10}{:>11.3?}{:>11.3?}{:>11.3?}", t.min, t.median, t.max);
+    }
+}
+
+/// Prints both parts' answers, `-` for a part without one.
+pub fn print_answers(part1: &Answer, part2: &Answer) {
+    println!("Part 1: {part1}");
+    println!("Part 2: {part2}");
+}
+
+pub fn print_memory(memory: &[Usage; 2]) {
+    for (part, m) in memory.iter().enumerate() {
+        println!("Part {} heap: {} peak, {} allocations", part + 1, memory::human(m.peak), m.allocations);
+    }
+}
+
+pub fn print_assumptions(assumptions: &[Assumption]) {
+    if assumptions.is_empty() {
+        println!("No assumptions to check");
+    }
+    for a in assumptions {
+        match &a.failure {
+            None => println!("Holds: {}", a.what),
+            Some(found) => println!("FAILS: {} ({found})", a.what),
+        }
+    }
+}
+
+/// Prints diagnostics as a table, a column for each field.
+pub fn print_diagnostics(rows: &[JsonValue]) {
+    let Some(first) = rows.first() else {
+        println!("No diagnostics for this day");
+        return;
+    };
+    let names: Vec<&str> = first.entries().map(|(name, _)| name).collect();
+    let cells: Vec<Vec<String>> = rows.iter()
+        .map(|row| names.iter()
+            .map(|name| if row[*name].is_null() { "-".to_string() } else { row[*name].to_string() })
+            .collect())
+        .collect();
+    let widths: Vec<usize> = names.iter().enumerate()
+        .map(|(col, name)| cells.iter().map(|r| r[col].chars().count()).chain([name.len()]).max().unwrap())
+        .collect();
+    let line = |fields: Vec<&str>| {
+        let padded: Vec<String> = fields.iter().zip(&widths).map(|(f, w)| format!("{f:<w$}")).collect();
+        println!("{}", padded.join("  ").trim_end());
+    };
+    line(names.clone());
+    for row in &cells {
+        line(row.iter().map(String::as_str).collect());
+    }
+}
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        match value {
+            Answer::Int(n) => i64::try_from(*n).map(JsonValue::from)
+                .or_else(|_| u64::try_from(*n).map(JsonValue::from))
+                .unwrap_or_else(|_| n.to_string().into()),
+            Answer::Big(n) => n.to_string().into(),
+            Answer::Unsolved(_) | Answer::None => JsonValue::Null,
+        }
+    }
+}
+
+pub fn solution_json(day: u32, sol: &Solution) -> JsonValue {
+    let mut json = object!{
+        day: day,
+        part1: &sol.part1,
+        part2: &sol.part2,
+        elapsed_ms: ms(sol.elapsed),
+    };
+    if let Some(memory) = &sol.memory {
+        json["memory"] = memory.iter()
+            .map(|m| object!{ peak_bytes: m.peak, allocations: m.allocations })
+            .collect::<Vec<_>>()
+            .into();
+    }
+    if let Some(assumptions) = &sol.assumptions {
+        json["assumptions"] = assumptions.iter()
+            .map(|a| object!{ assumption: a.what, holds: a.holds(), found: a.failure.as_deref() })
+            .collect::<Vec<_>>()
+            .into();
+    }
+    if let Some(diagnostics) = &sol.diagnostics {
+        json["diagnostics"] = diagnostics.clone().into();
+    }
+    json
+}
+
+fn ms(d: Duration) -> f64 {
+    d.as_micros() as f64 / 1000.0
+}
+
+fn timing_json(t: &Timing) -> JsonValue {
+    object!{
+        min_ms: ms(t.min),
+        median_ms: ms(t.median),
+        max_ms: ms(t.max),
+    }
+}
+
+pub fn bench_json(bench: &Bench) -> JsonValue {
+    object!{
+        runs: bench.runs,
+        parse: timing_json(&bench.parse),
+        part1: timing_json(&bench.part1),
+        part2: timing_json(&bench.part2),
+    }
+}
+
+/// Appends a benchmark result to `path` as a line of JSON, stamped with
+/// the time so results from different runs can be told apart.
+fn write_bench(path: &Path, mut result: JsonValue) -> io::Result<()> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    result["timestamp"] = now.into();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{result}")
+}
+
+/// What running a day produced: its answers, and its timings if
+/// benchmarking.
+pub type Outcome = Result<(Solution, Option<Bench>), Error>;
+
+/// Solves `day`, or benchmarks it if the options ask for that.
+pub fn execute(day: &dyn DynDay, opts: &Options) -> Outcome {
+    match opts.bench {
+        Some(runs) => bench(day, opts, runs).map(|(sol, b)| (sol, Some(b))),
+        None => solve(day, opts).map(|sol| (sol, None)),
+    }
+}
+
+/// Solves `day` and prints its answers, reporting failures on stderr.
+/// Returns false if the day could not be run.
+pub fn run(day: &dyn DynDay, opts: &Options) -> bool {
+    report(day, opts, execute(day, opts))
+}
+
+/// Prints the outcome of [`execute`]. Returns false if it was a failure.
+pub fn report(day: &dyn DynDay, opts: &Options, outcome: Outcome) -> bool {
+    match outcome {
+        Ok((sol, bench)) => {
+            let mut json = solution_json(day.day(), &sol);
+            if let Some(b) = &bench {
+                json["bench"] = bench_json(b);
+            }
+            match opts.format {
+                Format::Text => {
+                    print_answers(&sol.part1, &sol.part2);
+                    if let Some(m) = &sol.memory {
+                        print_memory(m);
+                    }
+                    if let Some(a) = &sol.assumptions {
+                        print_assumptions(a);
+                    }
+                    if let Some(d) = &sol.diagnostics {
+                        print_diagnostics(d);
+                    }
+                    if let Some(b) = &bench {
+                        print_bench(b);
+                    }
+                },
+                Format::Json => println!("{json}"),
+            }
+            if let Some(path) = &opts.bench_out {
+                if let Err(e) = write_bench(path, json) {
+                    eprintln!("day{:02}: {}: {e}", day.day(), path.display());
+                    return false;
+                }
+            }
+            let failed = sol.assumptions.iter().flatten().filter(|a| !a.holds()).count();
+            if failed > 0 {
+                eprintln!("day{:02}: {failed} assumption(s) about the input don't hold; the answers may be wrong", day.day());
+            }
+            failed == 0
+        },
+        Err(e) => {
+            let msg = format!("{}: {e}", opts.source.describe(day.day()));
+            if opts.format == Format::Json {
+                println!("{}", object!{ day: day.day(), error: msg.as_str() });
+            }
+            eprintln!("day{:02}: {msg}", day.day());
+            false
+        },
+    }
+}
+
+/// Entry point for the single-day binaries.
+pub fn day_main(day: u32) -> ExitCode {
+    let opts = match Options::parse(std::env::args().skip(1)) {
+        Ok(opts) => opts,
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("usage: day{day:02} {OPTIONS_USAGE}");
+            return ExitCode::from(2);
+        },
+    };
+    if run(days::get(day).unwrap(), &opts) {
+        ExitCode::SUCCESS
+    }
+    else {
+        ExitCode::FAILURE
+    }
+}
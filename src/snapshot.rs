@@ -0,0 +1,68 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use crate::days;
+use crate::runner::{Context, DynDay};
+
+/// Where a day's snapshot is kept: next to its example inputs, as
+/// `dayNN.snapshot`.
+pub fn path(day: u32) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(format!("src/days/day{day:02}.snapshot"))
+}
+
+/// A day's snapshot of its first example, if it takes one.
+pub fn take(day: &dyn DynDay) -> Option<String> {
+    let example = day.examples().first()?;
+    let input = day.parse(example.lines().map(String::from).collect())
+        .unwrap_or_else(|e| panic!("day{:02} example: {e}", day.day()));
+    day.snapshot(&*input, &Context::default())
+}
+
+/// Where two snapshots first differ.
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => { line += 1; },
+            (e, a) => {
+                return format!("line {line}: expected {:?}, got {:?}", e.unwrap_or("<end>"), a.unwrap_or("<end>"));
+            },
+        }
+    }
+}
+
+/// Compares every day's snapshot with the one on file, returning the days
+/// that don't match and how. With `update`, those files are rewritten.
+pub fn check(update: bool) -> io::Result<Vec<(u32, String)>> {
+    let mut changed = Vec::new();
+    for day in days::all() {
+        let Some(actual) = take(day) else { continue };
+        let path = path(day.day());
+        let problem = match fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => { continue; },
+            Ok(expected) => first_difference(&expected, &actual),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => format!("no snapshot at {}", path.display()),
+            Err(e) => { return Err(e); },
+        };
+        if update {
+            fs::write(&path, &actual)?;
+        }
+        changed.push((day.day(), problem));
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_test() {
+        let changed = check(false).unwrap();
+        let report: Vec<String> = changed.iter().map(|(day, p)| format!("day{day:02}: {p}")).collect();
+        assert!(changed.is_empty(), "{}\nrun `aoc snapshot --update` to accept the changes", report.join("\n"));
+        assert_eq!(first_difference("a\nb\n", "a\n"), "line 2: expected \"b\", got \"<end>\"");
+    }
+}
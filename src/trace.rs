@@ -0,0 +1,116 @@
+use std::fmt;
+use std::io::{self, BufWriter, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use json::{object, JsonValue};
+
+/// The event log for `--trace`: one JSON line per simulation event,
+/// numbered by a step counter, optionally cut off after a given step.
+pub struct Trace {
+    until: Option<usize>,
+    step: AtomicUsize,
+    part: AtomicUsize,
+    stopped: AtomicBool,
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl Trace {
+    pub fn new(out: Box<dyn Write + Send>, until: Option<usize>) -> Self {
+        Self {
+            until,
+            step: AtomicUsize::new(0),
+            part: AtomicUsize::new(0),
+            stopped: AtomicBool::new(false),
+            out: Mutex::new(out),
+        }
+    }
+
+    /// A trace written to stderr, leaving stdout for the answers.
+    pub fn stderr(until: Option<usize>) -> Self {
+        Self::new(Box::new(BufWriter::new(io::stderr())), until)
+    }
+
+    /// Sets which part later events belong to.
+    pub fn set_part(&self, part: usize) {
+        self.part.store(part, Ordering::Relaxed);
+    }
+
+    /// Whether the `until` step has been passed.
+    pub fn stopped(&self) -> bool {
+        self.stopped.load(Ordering::Relaxed)
+    }
+
+    /// Writes an event, with the fields of `data` (a JSON object) added
+    /// to it. Returns false, writing nothing, once past the `until` step.
+    pub fn event(&self, event: &str, data: JsonValue) -> bool {
+        if self.stopped() {
+            return false;
+        }
+        let step = self.step.fetch_add(1, Ordering::Relaxed) + 1;
+        if self.until.is_some_and(|u| step > u) {
+            self.stopped.store(true, Ordering::Relaxed);
+            return false;
+        }
+        let mut line = object!{
+            step: step,
+            part: self.part.load(Ordering::Relaxed),
+            event: event,
+        };
+        for (k, v) in data.entries() {
+            line[k] = v.clone();
+        }
+        // a trace that can't be written isn't worth failing the run over
+        let _ = writeln!(self.out.lock().unwrap(), "{}", line.dump());
+        true
+    }
+}
+
+impl fmt::Debug for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Trace")
+            .field("until", &self.until)
+            .field("step", &self.step)
+            .field("stopped", &self.stopped)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::days;
+    use crate::runner::{Answer, Context};
+
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn trace_test() {
+        let day = days::get(14).unwrap();
+        let input = day.parse(day.examples()[0].lines().map(String::from).collect()).unwrap();
+        let log = Shared::default();
+        let ctx = Context { trace: Some(Arc::new(Trace::new(Box::new(log.clone()), Some(5)))), ..Context::default() };
+        day.part1(&*input, &ctx);
+        assert!(ctx.stopped());
+
+        let log = String::from_utf8(log.0.lock().unwrap().clone()).unwrap();
+        let events: Vec<JsonValue> = log.lines().map(|l| json::parse(l).unwrap()).collect();
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[4]["step"], 5);
+        assert_eq!(events[0]["event"], "rock_moved");
+
+        let ctx = Context::default();
+        assert!(ctx.trace("ignored", || unreachable!()));
+        assert_eq!(day.part1(&*input, &ctx), Answer::Int(136));
+    }
+}
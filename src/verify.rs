@@ -0,0 +1,210 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use itertools::Itertools;
+use json::JsonValue;
+use crate::budget::Budget;
+use crate::days;
+use crate::runner::{self, Answer, DynDay, Options, Source};
+
+/// The answers for the examples, kept at the top of the repository.
+pub fn examples_manifest() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("answers.json")
+}
+
+/// The manifests `aoc verify` checks when not given one: the examples',
+/// and the one for the real inputs, `answers.json` in the current
+/// directory beside the `dayNN.input` files, if there is one.
+pub fn default_manifests() -> Vec<PathBuf> {
+    let examples = examples_manifest();
+    let inputs = PathBuf::from("answers.json");
+    let same = |a: &Path, b: &Path| matches!((a.canonicalize(), b.canonicalize()), (Ok(a), Ok(b)) if a == b);
+    let mut paths = vec![examples.clone()];
+    if inputs.exists() && !same(&inputs, &examples) {
+        paths.push(inputs);
+    }
+    paths
+}
+
+/// The known answers for one input file. A part with no recorded answer
+/// isn't checked; a recorded `null` means the part has no answer.
+#[derive(Clone, Debug)]
+pub struct Expected {
+    pub input: PathBuf,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// Known answers by day, read from a JSON file of the form
+/// `{"1": {"day01.input": {"part1": 123, "part2": 456}}, ...}`.
+/// Input paths are relative to the directory of the manifest.
+pub type Manifest = BTreeMap<u32, Vec<Expected>>;
+
+fn expected_answer(v: &JsonValue) -> Option<String> {
+    match v {
+        JsonValue::Null => Some(Answer::None.to_string()),
+        v => Some(v.to_string()),
+    }
+}
+
+pub fn parse_manifest(text: &str, base: &Path) -> Result<Manifest, String> {
+    let root = json::parse(text).map_err(|e| e.to_string())?;
+    if !root.is_object() {
+        return Err("expected an object keyed by day".into());
+    }
+    let mut manifest = Manifest::new();
+    for (day, inputs) in root.entries() {
+        let day_num = day.parse::<u32>().ok()
+            .filter(|d| days::get(*d).is_some())
+            .ok_or_else(|| format!("no such day: {day}"))?;
+        if !inputs.is_object() {
+            return Err(format!("day {day}: expected an object keyed by input file"));
+        }
+        let entry = manifest.entry(day_num).or_default();
+        for (input, parts) in inputs.entries() {
+            let get = |part: &str| if parts.has_key(part) {
+                expected_answer(&parts[part])
+            } else {
+                None
+            };
+            entry.push(Expected {
+                input: base.join(input),
+                part1: get("part1"),
+                part2: get("part2"),
+            });
+        }
+    }
+    Ok(manifest)
+}
+
+pub fn load_manifest(path: &Path) -> Result<Manifest, String> {
+    let text = fs::read_to_string(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => format!(
+            "{}: {e}; write one like {{\"1\": {{\"day01.input\": {{\"part1\": 123, \"part2\": 456}}}}}}",
+            path.display(),
+        ),
+        _ => format!("{}: {e}", path.display()),
+    })?;
+    let base = path.parent().unwrap_or(Path::new(""));
+    parse_manifest(&text, base).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Loads several manifests as one.
+pub fn load_manifests(paths: &[PathBuf]) -> Result<Manifest, String> {
+    let mut manifest = Manifest::new();
+    for path in paths {
+        for (day, inputs) in load_manifest(path)? {
+            manifest.entry(day).or_default().extend(inputs);
+        }
+    }
+    Ok(manifest)
+}
+
+/// The result of checking one day against one input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Pass,
+    /// What differed, or why the day couldn't be run.
+    Fail(Vec<String>),
+    /// There's nothing to check against.
+    Missing(String),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "PASS"),
+            Outcome::Fail(diffs) => write!(f, "FAIL: {}", diffs.join("; ")),
+            Outcome::Missing(why) => write!(f, "MISSING: {why}"),
+        }
+    }
+}
+
+fn diff(part: u32, expected: &Option<String>, got: &Answer) -> Option<String> {
+    let expected = expected.as_ref()?;
+    let got = got.to_string();
+    (*expected != got).then(|| format!("part {part}: expected {expected}, got {got}"))
+}
+
+/// Solves `day` against `expected.input` within `budget`, as `aoc run`
+/// would, and compares the answers.
+pub fn check(day: &dyn DynDay, expected: &Expected, budget: Budget) -> Outcome {
+    if !expected.input.exists() {
+        return Outcome::Missing(format!("{} not found", expected.input.display()));
+    }
+    let opts = Options { source: Source::Path(expected.input.clone()), budget, ..Options::default() };
+    match runner::solve(day, &opts) {
+        Ok(sol) => {
+            let diffs: Vec<String> = [
+                diff(1, &expected.part1, &sol.part1),
+                diff(2, &expected.part2, &sol.part2),
+            ].into_iter().flatten().collect();
+            if diffs.is_empty() { Outcome::Pass } else { Outcome::Fail(diffs) }
+        },
+        Err(e) => Outcome::Fail(vec![e.to_string()]),
+    }
+}
+
+/// Checks every day against its known answers within `budget`, printing
+/// a line for each. Days with no answers recorded are listed at the end
+/// rather than counted as missing, since nothing was expected of them.
+/// Returns false if any answer was wrong.
+pub fn verify(manifest: &Manifest, budget: Budget) -> bool {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    let mut unrecorded = Vec::new();
+    for day in days::all() {
+        let Some(inputs) = manifest.get(&day.day()) else {
+            unrecorded.push(day.day());
+            continue;
+        };
+        for expected in inputs {
+            let outcome = check(day, expected, budget);
+            match outcome {
+                Outcome::Pass => { passed += 1; },
+                Outcome::Fail(_) => { failed += 1; },
+                Outcome::Missing(_) => { missing += 1; },
+            }
+            println!("day{:02}  {}  {outcome}", day.day(), expected.input.display());
+        }
+    }
+    println!("{passed} passed, {failed} failed, {missing} missing");
+    if !unrecorded.is_empty() {
+        println!("no answers recorded for days {}", unrecorded.iter().join(", "));
+    }
+    failed == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_test() {
+        let base = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/days");
+        let manifest = parse_manifest(r#"{
+            "9": {"day09.testinput": {"part1": 114, "part2": 2}},
+            "25": {"day25.testinput": {"part2": null}, "nonesuch.input": {}},
+            "5": {"day05.testinput": {"part1": 35, "part2": 47}}
+        }"#, &base).unwrap();
+        assert_eq!(check(days::get(9).unwrap(), &manifest[&9][0], Budget::default()), Outcome::Pass);
+        assert!(matches!(check(days::get(25).unwrap(), &manifest[&25][1], Budget::default()), Outcome::Missing(_)));
+        assert_eq!(
+            check(days::get(5).unwrap(), &manifest[&5][0], Budget::default()),
+            Outcome::Fail(vec!["part 2: expected 47, got 46".into()]),
+        );
+        assert!(parse_manifest(r#"{"26": {}}"#, &base).is_err());
+        assert!(load_manifest(&base.join("nonesuch.json")).unwrap_err().contains("write one like"));
+
+        let examples = load_manifest(&examples_manifest()).unwrap();
+        assert_eq!(examples[&1][1].part2.as_deref(), Some("281"));
+        assert_eq!(examples[&25][0].part2.as_deref(), Some("-"));
+        assert_eq!(default_manifests()[0], examples_manifest());
+
+        let budget = Budget { iterations: Some(1), ..Budget::default() };
+        assert!(matches!(check(days::get(25).unwrap(), &manifest[&25][0], budget), Outcome::Fail(_)));
+        let both = load_manifests(&[examples_manifest(), examples_manifest()]).unwrap();
+        assert_eq!(both[&9].len(), 2);
+    }
+}